@@ -1,8 +1,44 @@
 //! This crate provides a mechanism for storing data as entities in designated [data worlds](DataWorlds).
-use bevy_ecs::{prelude::*, system::RunSystemOnce};
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{self, Read, Write},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use bevy_app::prelude::*;
+
+use bevy_ecs::{
+    component::{ComponentId, Tick},
+    entity::EntityHashMap,
+    prelude::*,
+    system::RunSystemOnce,
+};
 use bevy_log::prelude::*;
-use bevy_reflect::{std_traits::ReflectDefault, Reflect};
-use bevy_scene::{ron::Error as RonError, DynamicScene, DynamicSceneBundle};
+use bevy_reflect::{
+    std_traits::ReflectDefault, FromReflect, GetTypeRegistration, Reflect, ReflectDeserialize,
+    ReflectMut, ReflectRef, ReflectSerialize, TypeInfo,
+};
+use bevy_scene::{
+    ron::{self, de::SpannedError, ser::PrettyConfig, Error as RonError},
+    serde::{SceneDeserializer, SceneSerializer},
+    DynamicEntity, DynamicScene, DynamicSceneBuilder, DynamicSceneBundle, SceneFilter,
+    SceneSpawnError,
+};
+use bevy_tasks::{IoTaskPool, Task, TaskPool};
+use bevy_utils::tracing::field;
+use bincode::{Error as BincodeError, Options};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use serde::{
+    de::DeserializeSeed, de::Error as _, Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::Error as JsonError;
 // TODO: rename worlds into static, persistent, transient
 /// Mutable data retrieved from a [DataWorld](data worlds) resource.
 pub enum DataMut<'a> {
@@ -10,49 +46,979 @@ pub enum DataMut<'a> {
     Missing,
     /// Data was found.
     Found(EntityWorldMut<'a>),
-    /// Data was in the static world and was moved to the dynamic world.
-    Moved(EntityWorldMut<'a>, DataRef),
+    /// Data was in the static world and was moved to a dynamic layer.
+    Moved {
+        /// The moved data.
+        entity: EntityWorldMut<'a>,
+        /// Reference that was used to access the data.
+        old: DataRef,
+        /// Reference to the new location of the data.
+        new: DataRef,
+    },
+}
+impl<'a> DataMut<'a> {
+    /// Returns the data regardless of whether it was moved, or [`None`] when it does not exist.
+    #[inline]
+    pub fn ok(self) -> Option<EntityWorldMut<'a>> {
+        match self {
+            Self::Missing => None,
+            Self::Found(entity) | Self::Moved { entity, .. } => Some(entity),
+        }
+    }
+    /// Returns `true` if the data was moved to a dynamic layer by this access.
+    #[inline]
+    pub fn is_moved(&self) -> bool {
+        matches!(self, Self::Moved { .. })
+    }
+    /// Returns the reference to the new location of the data, if it was moved by this access.
+    #[inline]
+    pub fn new_ref(&self) -> Option<DataRef> {
+        match self {
+            Self::Moved { new, .. } => Some(*new),
+            _ => None,
+        }
+    }
+}
+
+/// Lazy mutable access to data retrieved from a [DataWorlds] resource, see [`peek_mut`](DataWorlds::peek_mut).
+///
+/// Static data is only transferred to a dynamic layer when a component is accessed mutably for the first time,
+/// so dropping the guard without writing leaves the static data untouched.
+pub struct DataPeekMut<'a> {
+    data: &'a mut DataWorlds,
+    ptr: DataRef,
+}
+impl DataPeekMut<'_> {
+    /// Returns the current location of the data, this will be dynamic after the first write to static data.
+    #[inline]
+    pub fn data_ref(&self) -> DataRef {
+        self.data.resolve(self.ptr)
+    }
+    /// Returns `true` when the data was transferred from the static world.
+    #[inline]
+    pub fn is_transferred(&self) -> bool {
+        self.ptr.is_static() && self.data_ref().is_dynamic()
+    }
+    /// Returns a reference to the component `T` without transferring the data.
+    #[inline]
+    pub fn get<T: Component>(&self) -> Option<&T> {
+        self.data.get_component::<T>(self.ptr)
+    }
+    /// Returns a guard for mutable access to the component `T`, which transfers static data when it is first written to.
+    /// Returns [`None`] when the data does not have the component or the static data can not be transferred.
+    pub fn get_mut<T: Component>(&mut self) -> Option<ComponentPeekMut<'_, T>> {
+        self.data.get_component::<T>(self.ptr)?;
+        if let DataRef::Static(entity) = self.ptr {
+            if self.data.needs_transfer(entity) {
+                if let Err(err) = self.data.check_transfer(entity) {
+                    warn!("{err}");
+                    return None;
+                }
+                if !self.data.resolve(self.ptr).is_dynamic()
+                    && !self
+                        .data
+                        .is_transferable(TypeId::of::<T>(), std::any::type_name::<T>())
+                {
+                    return None;
+                }
+            }
+        }
+        Some(ComponentPeekMut {
+            worlds: self.data,
+            ptr: self.ptr,
+            location: None,
+            pending: None,
+            marker: PhantomData,
+        })
+    }
+}
+
+/// Lazy mutable access to a single component, see [`DataPeekMut::get_mut`].
+///
+/// Static data is copied into the active dynamic layer when the component is dereferenced mutably for the first time,
+/// references to the static original are forwarded to the copy once the guard is dropped.
+pub struct ComponentPeekMut<'a, T: Component> {
+    worlds: &'a mut DataWorlds,
+    ptr: DataRef,
+    location: Option<(LayerId, Entity)>,
+    pending: Option<Entity>,
+    marker: PhantomData<T>,
+}
+impl<T: Component> Deref for ComponentPeekMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        let component = match self.location {
+            Some((layer, entity)) => self.worlds.dynamic_layers[&layer].get::<T>(entity),
+            None => self.worlds.get_component::<T>(self.ptr),
+        };
+        component.expect("Peeked component should exist")
+    }
+}
+impl<T: Component> DerefMut for ComponentPeekMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        let (layer, entity) = match self.location {
+            Some(location) => location,
+            None => {
+                let location = match (self.ptr, self.worlds.resolve(self.ptr)) {
+                    (DataRef::Static(entity), _) if self.worlds.needs_transfer(entity) => {
+                        self.pending = Some(entity);
+                        self.worlds
+                            .copy_to_active(entity)
+                            .expect("Peeked data should be transferable")
+                    }
+                    (_, DataRef::Dynamic(layer, entity)) => (layer, entity),
+                    _ => unreachable!("Peeked data should exist"),
+                };
+                *self.location.insert(location)
+            }
+        };
+        self.worlds
+            .dynamic_layers
+            .get_mut(&layer)
+            .and_then(|world| world.get_mut::<T>(entity))
+            .expect("Peeked component should exist")
+            .into_inner()
+    }
+}
+impl<T: Component> Drop for ComponentPeekMut<'_, T> {
+    fn drop(&mut self) {
+        if let (Some(source), Some(target)) = (self.pending, self.location) {
+            self.worlds.record_transfer(source, target);
+        }
+    }
+}
+
+/// Error that can occur while loading data from a file.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The file does not contain valid RON.
+    Syntax(SpannedError),
+    /// The RON could not be deserialized into a scene.
+    Ron(RonError),
+    /// The JSON could not be deserialized into a scene.
+    Json(JsonError),
+    /// The binary data could not be deserialized into a scene.
+    Binary(BincodeError),
+    /// The data does not start with the expected header.
+    InvalidHeader,
+    /// The data was written using an unsupported format version.
+    UnsupportedVersion(u16),
+    /// The deserialized scene could not be written into the data world.
+    Spawn(SceneSpawnError),
+    /// The data could not be deserialized using a [DataFormat].
+    Format(FormatError),
+    /// The components in the data do not match the registered types, see [`load_dynamic_strict`](DataWorlds::load_dynamic_strict).
+    SchemaMismatch(Vec<SchemaMismatch>),
+}
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read data: {err}"),
+            Self::Syntax(err) => write!(f, "failed to parse data: {err}"),
+            Self::Ron(err) => write!(f, "failed to deserialize data: {err}"),
+            Self::Json(err) => write!(f, "failed to deserialize data: {err}"),
+            Self::Binary(err) => write!(f, "failed to deserialize data: {err}"),
+            Self::InvalidHeader => write!(f, "data has an invalid header"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "data has unsupported format version {version}")
+            }
+            Self::Spawn(err) => write!(f, "failed to spawn data: {err}"),
+            Self::Format(err) => write!(f, "failed to deserialize data: {err}"),
+            Self::SchemaMismatch(mismatches) => {
+                write!(f, "data does not match registered types:")?;
+                for mismatch in mismatches {
+                    write!(f, " {mismatch};")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Syntax(err) => Some(err),
+            Self::Ron(err) => Some(err),
+            Self::Json(err) => Some(err),
+            Self::Binary(err) => Some(err),
+            Self::InvalidHeader | Self::UnsupportedVersion(_) | Self::SchemaMismatch(_) => None,
+            Self::Spawn(err) => Some(err),
+            Self::Format(err) => Some(err),
+        }
+    }
+}
+impl From<io::Error> for LoadError {
+    #[inline]
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+impl From<SpannedError> for LoadError {
+    #[inline]
+    fn from(value: SpannedError) -> Self {
+        Self::Syntax(value)
+    }
+}
+impl From<RonError> for LoadError {
+    #[inline]
+    fn from(value: RonError) -> Self {
+        Self::Ron(value)
+    }
+}
+impl From<JsonError> for LoadError {
+    #[inline]
+    fn from(value: JsonError) -> Self {
+        Self::Json(value)
+    }
+}
+impl From<BincodeError> for LoadError {
+    #[inline]
+    fn from(value: BincodeError) -> Self {
+        Self::Binary(value)
+    }
+}
+impl From<SceneSpawnError> for LoadError {
+    #[inline]
+    fn from(value: SceneSpawnError) -> Self {
+        Self::Spawn(value)
+    }
+}
+impl From<FormatError> for LoadError {
+    #[inline]
+    fn from(value: FormatError) -> Self {
+        Self::Format(value)
+    }
+}
+
+/// Difference between a component stored in a scene and its registered type, see [`LoadError::SchemaMismatch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaMismatch {
+    /// The type is not registered in the type registry.
+    Unregistered(String),
+    /// The scene contains a field that the registered type does not have.
+    UnknownField {
+        /// Type path of the component or resource.
+        type_path: String,
+        /// Name of the field.
+        field: String,
+    },
+    /// The scene does not contain a field of the registered type.
+    MissingField {
+        /// Type path of the component or resource.
+        type_path: String,
+        /// Name of the field.
+        field: String,
+    },
+}
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unregistered(type_path) => write!(f, "type {type_path} is not registered"),
+            Self::UnknownField { type_path, field } => {
+                write!(f, "type {type_path} has no field {field}")
+            }
+            Self::MissingField { type_path, field } => {
+                write!(f, "field {field} of type {type_path} is missing")
+            }
+        }
+    }
+}
+
+/// Error that can occur while serializing data into binary format.
+#[derive(Debug)]
+pub enum SerError {
+    /// The data could not be encoded.
+    Bincode(BincodeError),
+}
+impl fmt::Display for SerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bincode(err) => write!(f, "failed to serialize data: {err}"),
+        }
+    }
+}
+impl Error for SerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Bincode(err) => Some(err),
+        }
+    }
+}
+impl From<BincodeError> for SerError {
+    #[inline]
+    fn from(value: BincodeError) -> Self {
+        Self::Bincode(value)
+    }
+}
+
+/// Error that can occur while serializing data with a round-trip check, see [`serialize_dynamic_ron_checked`](DataWorlds::serialize_dynamic_ron_checked).
+#[derive(Debug)]
+pub enum SerCheckError {
+    /// The data could not be serialized.
+    Ron(RonError),
+    /// The serialized data could not be loaded again.
+    RoundTrip(LoadError),
+}
+impl fmt::Display for SerCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ron(err) => write!(f, "failed to serialize data: {err}"),
+            Self::RoundTrip(err) => write!(f, "serialized data can not be loaded: {err}"),
+        }
+    }
+}
+impl Error for SerCheckError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Ron(err) => Some(err),
+            Self::RoundTrip(err) => Some(err),
+        }
+    }
+}
+impl From<RonError> for SerCheckError {
+    #[inline]
+    fn from(value: RonError) -> Self {
+        Self::Ron(value)
+    }
+}
+impl From<LoadError> for SerCheckError {
+    #[inline]
+    fn from(value: LoadError) -> Self {
+        Self::RoundTrip(value)
+    }
+}
+
+/// Error that can occur while converting data using a [DataFormat].
+#[derive(Debug)]
+pub enum FormatError {
+    /// The data is not valid RON.
+    Syntax(SpannedError),
+    /// The data could not be converted from or to RON.
+    Ron(RonError),
+    /// The data could not be converted from or to JSON.
+    Json(JsonError),
+    /// Error of a custom format.
+    Custom(Box<dyn Error + Send + Sync>),
+}
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax(err) => write!(f, "failed to parse data: {err}"),
+            Self::Ron(err) => write!(f, "failed to convert data: {err}"),
+            Self::Json(err) => write!(f, "failed to convert data: {err}"),
+            Self::Custom(err) => write!(f, "failed to convert data: {err}"),
+        }
+    }
+}
+impl Error for FormatError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Syntax(err) => Some(err),
+            Self::Ron(err) => Some(err),
+            Self::Json(err) => Some(err),
+            Self::Custom(err) => Some(err.as_ref()),
+        }
+    }
+}
+impl From<SpannedError> for FormatError {
+    #[inline]
+    fn from(value: SpannedError) -> Self {
+        Self::Syntax(value)
+    }
+}
+impl From<RonError> for FormatError {
+    #[inline]
+    fn from(value: RonError) -> Self {
+        Self::Ron(value)
+    }
+}
+impl From<JsonError> for FormatError {
+    #[inline]
+    fn from(value: JsonError) -> Self {
+        Self::Json(value)
+    }
+}
+
+/// Format used to serialize and deserialize the data of a world, see [`serialize_dynamic`](DataWorlds::serialize_dynamic).
+pub trait DataFormat {
+    /// Serializes `scene` using the types registered in `type_registry`.
+    fn serialize(
+        &self,
+        scene: &DynamicScene,
+        type_registry: &AppTypeRegistry,
+    ) -> Result<Vec<u8>, FormatError>;
+    /// Deserializes a scene from `bytes` using the types registered in `type_registry`.
+    fn deserialize(
+        &self,
+        bytes: &[u8],
+        type_registry: &AppTypeRegistry,
+    ) -> Result<DynamicScene, FormatError>;
+}
+
+/// [DataFormat] for human readable RON.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RonFormat;
+impl DataFormat for RonFormat {
+    fn serialize(
+        &self,
+        scene: &DynamicScene,
+        type_registry: &AppTypeRegistry,
+    ) -> Result<Vec<u8>, FormatError> {
+        Ok(scene.serialize_ron(type_registry)?.into_bytes())
+    }
+    fn deserialize(
+        &self,
+        bytes: &[u8],
+        type_registry: &AppTypeRegistry,
+    ) -> Result<DynamicScene, FormatError> {
+        let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+        Ok(SceneDeserializer {
+            type_registry: &type_registry.read(),
+        }
+        .deserialize(&mut deserializer)?)
+    }
+}
+
+/// [DataFormat] for JSON.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonFormat;
+impl DataFormat for JsonFormat {
+    fn serialize(
+        &self,
+        scene: &DynamicScene,
+        type_registry: &AppTypeRegistry,
+    ) -> Result<Vec<u8>, FormatError> {
+        Ok(serde_json::to_vec_pretty(&SceneSerializer::new(
+            scene,
+            type_registry,
+        ))?)
+    }
+    fn deserialize(
+        &self,
+        bytes: &[u8],
+        type_registry: &AppTypeRegistry,
+    ) -> Result<DynamicScene, FormatError> {
+        let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+        Ok(SceneDeserializer {
+            type_registry: &type_registry.read(),
+        }
+        .deserialize(&mut deserializer)?)
+    }
+}
+
+/// Version of the data stored in a world, saved as a resource so it can be detected when loading.
+/// Data without this resource is considered to be version `0`.
+///
+/// This has to be registered in the type registry to be saved and loaded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource, Reflect)]
+#[reflect(Default, Resource)]
+pub struct DataVersion(pub u32);
+
+/// Transforms a scene saved with an older [version](DataVersion) into the shape of the next version,
+/// see [`load_dynamic_from_ron_with_migrations`](DataWorlds::load_dynamic_from_ron_with_migrations).
+pub trait SceneMigration {
+    /// Version of the scenes this migration can be applied to.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_version(&self) -> u32;
+    /// Transforms `scene` into the shape of the next version.
+    fn apply(&self, scene: &mut DynamicScene);
+}
+
+/// Error that can occur while merging a scene into existing data.
+#[derive(Debug)]
+pub enum MergeError {
+    /// The scene could not be written into the data world.
+    Spawn(SceneSpawnError),
+}
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Spawn(err) => write!(f, "failed to spawn data: {err}"),
+        }
+    }
+}
+impl Error for MergeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Spawn(err) => Some(err),
+        }
+    }
+}
+impl From<SceneSpawnError> for MergeError {
+    #[inline]
+    fn from(value: SceneSpawnError) -> Self {
+        Self::Spawn(value)
+    }
+}
+
+/// Error that can occur while accessing data in [DataWorlds].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataError {
+    /// Tried to access a [`Null`](DataRef::Null) reference.
+    NullReference,
+    /// The referenced data does not exist.
+    Missing(DataRef),
+    /// The referenced static data could not be transferred to the dynamic world.
+    Transfer(TransferError),
+    /// The data is still referenced by other data, see [`ref_count`](DataWorlds::ref_count).
+    Referenced {
+        /// The referenced data.
+        ptr: DataRef,
+        /// Number of references to the data.
+        count: usize,
+    },
+}
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NullReference => write!(f, "tried to access null reference"),
+            Self::Missing(ptr) => write!(f, "data {ptr:?} does not exist"),
+            Self::Transfer(err) => write!(f, "failed to transfer data: {err}"),
+            Self::Referenced { ptr, count } => {
+                write!(f, "data {ptr:?} is still referenced {count} times")
+            }
+        }
+    }
+}
+impl Error for DataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Transfer(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Error that can occur while transferring data between worlds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferError {
+    /// Tried to transfer a [`Null`](DataRef::Null) reference.
+    NullReference,
+    /// The data to transfer does not exist.
+    Missing(Entity),
+    /// Transfers are disabled, see [`set_frozen`](DataWorlds::set_frozen).
+    Frozen(Entity),
+    /// The component is not a rust type, so it can not be copied using reflection.
+    NotRustType {
+        /// Id of the offending component.
+        component_id: ComponentId,
+        /// Name of the offending component.
+        name: String,
+    },
+    /// The component type is not registered in the type registry.
+    Unregistered {
+        /// Id of the offending component.
+        component_id: ComponentId,
+        /// Type of the offending component.
+        type_id: TypeId,
+        /// Name of the offending component.
+        name: String,
+    },
+    /// The component type is registered, but does not reflect `Component`.
+    NotReflectComponent {
+        /// Id of the offending component.
+        component_id: ComponentId,
+        /// Type of the offending component.
+        type_id: TypeId,
+        /// Name of the offending component.
+        name: String,
+    },
+}
+impl fmt::Display for TransferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NullReference => write!(f, "tried to transfer null reference"),
+            Self::Missing(entity) => write!(f, "data {entity:?} does not exist"),
+            Self::Frozen(entity) => {
+                write!(f, "tried to transfer data {entity:?} while data worlds are frozen")
+            }
+            Self::NotRustType { name, .. } => {
+                write!(f, "component `{name}` is not a rust type")
+            }
+            Self::Unregistered { name, .. } => {
+                write!(f, "component `{name}` is not registered")
+            }
+            Self::NotReflectComponent { name, .. } => write!(
+                f,
+                "component `{name}` does not reflect `Component`, consider adding `#[reflect(Component)]` to the type"
+            ),
+        }
+    }
+}
+impl Error for TransferError {}
+
+/// Error that can occur while parsing a [DataRef] from its string form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDataRefError(String);
+impl fmt::Display for ParseDataRefError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid data reference {:?}", self.0)
+    }
+}
+impl Error for ParseDataRefError {}
+
+/// Error returned when references form a cycle, see [`iter_topological`](DataWorlds::iter_topological).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    /// All data that is part of the cycle, in reference order.
+    pub entities: Vec<DataRef>,
+}
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "references form a cycle:")?;
+        for ptr in &self.entities {
+            write!(f, " {ptr} ->")?;
+        }
+        match self.entities.first() {
+            Some(first) => write!(f, " {first}"),
+            None => Ok(()),
+        }
+    }
+}
+impl Error for CycleError {}
+
+/// Snapshot of all dynamic data in [DataWorlds], see [`snapshot`](DataWorlds::snapshot).
+pub struct DataSnapshot {
+    layers: Vec<(LayerId, DynamicScene)>,
+    next_layer: LayerId,
+    transferred: EntityHashMap<(LayerId, Entity)>,
+    shadowed: HashMap<LayerId, EntityHashMap<(LayerId, Entity)>>,
+}
+impl Clone for DataSnapshot {
+    fn clone(&self) -> Self {
+        Self {
+            layers: self
+                .layers
+                .iter()
+                .map(|(layer, scene)| (*layer, clone_scene(scene)))
+                .collect(),
+            next_layer: self.next_layer,
+            transferred: self.transferred.clone(),
+            shadowed: self.shadowed.clone(),
+        }
+    }
+}
+
+/// Reflected copies of all components of an entity, see [`extract_components`](DataWorlds::extract_components).
+pub struct ReflectedEntity {
+    components: ReflectedComponents,
+}
+impl ReflectedEntity {
+    /// Iterates over the reflected component values.
+    #[inline]
+    pub fn components(&self) -> impl Iterator<Item = &dyn Reflect> + '_ {
+        self.components.iter().map(|(_, value)| value.as_ref())
+    }
+}
+impl fmt::Debug for ReflectedEntity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.components()).finish()
+    }
+}
+
+/// Differences between transferred data and its static original, see [`diff_against_static`](DataWorlds::diff_against_static).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefDiff {
+    /// The static original.
+    pub source: Entity,
+    /// The dynamic copy.
+    pub target: DataRef,
+    /// Names of all components that were changed, added or removed in the dynamic copy.
+    pub changed: Vec<String>,
+}
+
+/// Approximate memory usage of the data worlds, see [`estimated_size`](DataWorlds::estimated_size).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DataSizeReport {
+    /// Number of entities in the static world.
+    pub static_entities: u32,
+    /// Number of entities in all dynamic layers.
+    pub dynamic_entities: u32,
+    /// Estimated size of all components in the static world in bytes.
+    pub static_bytes: usize,
+    /// Estimated size of all components in all dynamic layers in bytes.
+    pub dynamic_bytes: usize,
+}
+
+/// Event that is buffered whenever static data is transferred to a dynamic layer, see [`drain_transfer_events`](DataWorlds::drain_transfer_events).
+#[derive(Debug, Event, Clone, Copy, PartialEq, Eq)]
+pub struct DataTransferred {
+    /// The static original.
+    pub old: DataRef,
+    /// The dynamic copy.
+    pub new: DataRef,
+}
+
+/// Plugin that creates the [DataWorlds] resource, optionally loading data from RON files.
+///
+/// Files are loaded once all plugins are built, so components registered by other plugins can be loaded.
+/// When a file can not be loaded an error is logged and the corresponding world stays empty.
+#[derive(Debug, Default, Clone)]
+pub struct DataWorldsPlugin {
+    /// Path to a RON file containing the static data.
+    pub static_path: Option<PathBuf>,
+    /// Path to a RON file containing the dynamic data.
+    pub dynamic_path: Option<PathBuf>,
+}
+impl Plugin for DataWorldsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Entity>()
+            .register_type::<DataRef>()
+            .register_type::<LayerId>();
+    }
+    fn finish(&self, app: &mut App) {
+        let type_registry = app.world.resource::<AppTypeRegistry>().clone();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        if let Some(path) = &self.static_path {
+            if let Err(err) = data.load_static_from_file(&type_registry, path) {
+                error!("failed to load static data from {}: {err}", path.display());
+            }
+        }
+        if let Some(path) = &self.dynamic_path {
+            if let Err(err) = data.load_dynamic_from_file(&type_registry, path) {
+                error!("failed to load dynamic data from {}: {err}", path.display());
+            }
+        }
+        app.insert_resource(data);
+    }
+}
+
+/// Builder for [DataWorlds], see [`DataWorlds::builder`].
+pub struct DataWorldsBuilder {
+    type_registry: Option<AppTypeRegistry>,
+    static_scene: Option<DynamicSceneBundle>,
+    dynamic_scene: Option<DynamicSceneBundle>,
+    resource_filter: SceneFilter,
+}
+impl Default for DataWorldsBuilder {
+    fn default() -> Self {
+        Self {
+            type_registry: None,
+            static_scene: None,
+            dynamic_scene: None,
+            resource_filter: SceneFilter::allow_all().deny::<AppTypeRegistry>(),
+        }
+    }
+}
+impl DataWorldsBuilder {
+    /// Sets the type registry used by all data worlds, an empty registry is used when this is not set.
+    #[inline]
+    pub fn type_registry(mut self, type_registry: &AppTypeRegistry) -> Self {
+        self.type_registry = Some(type_registry.clone());
+        self
+    }
+    /// Sets the scene that will be spawned into the static world.
+    #[inline]
+    pub fn static_scene(mut self, static_scene: DynamicSceneBundle) -> Self {
+        self.static_scene = Some(static_scene);
+        self
+    }
+    /// Sets the scene that will be spawned into the [base layer](LayerId::BASE).
+    #[inline]
+    pub fn dynamic_scene(mut self, dynamic_scene: DynamicSceneBundle) -> Self {
+        self.dynamic_scene = Some(dynamic_scene);
+        self
+    }
+    /// Excludes the resource `R` from serialization, see [`DataWorlds::skip_resource`].
+    #[inline]
+    pub fn skip_resource<R: Resource>(mut self) -> Self {
+        self.resource_filter = self.resource_filter.deny::<R>();
+        self
+    }
+    /// Creates the configured [DataWorlds].
+    pub fn build(self) -> DataWorlds {
+        let type_registry = self.type_registry.unwrap_or_default();
+        let mut data =
+            DataWorlds::from_scenes(&type_registry, self.static_scene, self.dynamic_scene);
+        data.resource_filter = self.resource_filter;
+        data
+    }
 }
 
+/// Dynamic data of the active layer together with its transferred static data, see [`serialize_session`](DataWorlds::serialize_session).
+#[derive(Serialize, Deserialize)]
+struct Session {
+    scene: String,
+    transferred: Vec<(DataRef, DataRef)>,
+}
+
+/// System that is run on a dynamic layer after data was transferred into it, see [`set_on_transfer`](DataWorlds::set_on_transfer).
+struct TransferHook(Box<TransferHookFn>);
+type TransferHookFn = dyn Fn(&mut World, DataRef) + Send + Sync;
+
+/// Decides which components are copied when transferring data, see [`set_transfer_filter`](DataWorlds::set_transfer_filter).
+struct TransferFilter(Box<TransferFilterFn>);
+type TransferFilterFn = dyn Fn(TypeId, &str) -> bool + Send + Sync;
+
+/// Components copied when transferring data, together with the dynamic copy they are read from, if any.
+type TransferSource = (Option<(LayerId, Entity)>, Vec<ReflectComponent>);
+
+/// Transfers into a world that was removed from its layer using [`set_dynamic_world`](DataWorlds::set_dynamic_world),
+/// restored when the world is installed again.
+#[derive(Resource, Default)]
+struct DetachedTransfers(EntityHashMap<Entity>);
+
 /// Data storage separated into its own [world](World).
 /// Data will be separated into two world:
 /// - Static data is immutable
 /// - Dynamic data can be mutable
 ///
-/// Trying to access static data as mutable will first clone the data into the dynamic world.
-#[derive(Debug, Resource)]
+/// Dynamic data is organized in a stack of [layers](LayerId), each stored in its own world.
+/// The [base layer](LayerId::BASE) always exists, additional layers can be added using [`push_layer`](DataWorlds::push_layer).
+///
+/// Trying to access static data as mutable will first clone the data into the active dynamic layer.
+/// The location of cloned data is remembered, so references to the static original will be forwarded to the dynamic copy.
+/// Data that was cloned into a lower layer is cloned again into the active layer, so removing the active layer discards all changes made to it.
+#[derive(Resource)]
 pub struct DataWorlds {
     static_world: World,
-    dynamic_world: World,
+    dynamic_layers: HashMap<LayerId, World>,
+    layer_stack: Vec<LayerId>,
+    next_layer: LayerId,
+    transferred: EntityHashMap<(LayerId, Entity)>,
+    shadowed: HashMap<LayerId, EntityHashMap<(LayerId, Entity)>>,
+    resource_filter: SceneFilter,
+    transfer_events: Vec<DataTransferred>,
+    frozen: bool,
+    strict_transfer: bool,
+    transfer_skips: HashSet<TypeId>,
+    preserved_resources: HashMap<TypeId, fn(&mut World, &mut World)>,
+    indices: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    on_transfer: Option<TransferHook>,
+    transfer_filter: Option<TransferFilter>,
+    static_checkpoint: Option<(u64, Tick)>,
+}
+impl fmt::Debug for DataWorlds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let size = self.estimated_size();
+        f.debug_struct("DataWorlds")
+            .field("static_entities", &size.static_entities)
+            .field("dynamic_entities", &size.dynamic_entities)
+            .field("layers", &(self.layer_stack.len() + 1))
+            .field("active_layer", &self.active_layer())
+            .field("transferred", &self.transferred.len())
+            .field(
+                "registered_types",
+                &self.type_registry().read().iter().count(),
+            )
+            .field("frozen", &self.frozen)
+            .finish_non_exhaustive()
+    }
 }
 impl DataWorlds {
     /// Creates a `DataWorlds` resource from optional scene data.
     /// `type_registry` should have registered all components that will be stored in the data worlds.
+    ///
+    /// The dynamic scene will be placed in the [base layer](LayerId::BASE).
     #[inline]
     pub fn from_scenes(
         type_registry: &AppTypeRegistry,
         static_scene: Option<DynamicSceneBundle>,
         dynamic_scene: Option<DynamicSceneBundle>,
     ) -> Self {
-        let span_static = trace_span!("create_static_data_world").entered();
+        Self::from_scenes_with_capacity(type_registry, static_scene, dynamic_scene, 0, 0)
+    }
+    /// Creates an empty `DataWorlds` resource with a default type registry.
+    /// Types have to be added using [`register`](DataWorlds::register) before they can be transferred or serialized.
+    #[inline]
+    pub fn new_empty() -> Self {
+        Self::from_scenes(&AppTypeRegistry::default(), None, None)
+    }
+    /// Creates a `DataWorlds` resource and populates it by running `static_init` on the static world
+    /// and `dynamic_init` on the [base layer](LayerId::BASE).
+    /// `type_registry` should have registered all components that will be stored in the data worlds.
+    ///
+    /// This is an alternative to [`from_scenes`](DataWorlds::from_scenes) for programmatic setup.
+    pub fn from_fns(
+        type_registry: &AppTypeRegistry,
+        static_init: impl FnOnce(&mut World),
+        dynamic_init: impl FnOnce(&mut World),
+    ) -> Self {
+        let mut data = Self::from_scenes(type_registry, None, None);
+        static_init(&mut data.static_world);
+        dynamic_init(data.dynamic_world_mut());
+        data
+    }
+    /// Creates a `DataWorlds` resource from optional scene data, see [`from_scenes`](DataWorlds::from_scenes).
+    ///
+    /// Space for `static_capacity` static and `dynamic_capacity` dynamic entities is reserved up front.
+    /// This is only a performance hint, the worlds will still grow as needed.
+    pub fn from_scenes_with_capacity(
+        type_registry: &AppTypeRegistry,
+        static_scene: Option<DynamicSceneBundle>,
+        dynamic_scene: Option<DynamicSceneBundle>,
+        static_capacity: u32,
+        dynamic_capacity: u32,
+    ) -> Self {
+        let span_static =
+            trace_span!("create_static_data_world", entity_count = field::Empty).entered();
         let mut static_world = World::new();
+        reserve_entities(&mut static_world, static_capacity);
         static_world.insert_resource(type_registry.clone());
         if let Some(static_scene) = static_scene {
             static_world.spawn(static_scene);
         }
+        span_static.record("entity_count", static_world.entities().len());
         span_static.exit();
-        let span_dynamic = trace_span!("create_dynamic_data_world").entered();
+        let span_dynamic =
+            trace_span!("create_dynamic_data_world", entity_count = field::Empty).entered();
         let mut dynamic_world = World::new();
+        reserve_entities(&mut dynamic_world, dynamic_capacity);
         dynamic_world.insert_resource(type_registry.clone());
         if let Some(dynamic_scene) = dynamic_scene {
             dynamic_world.spawn(dynamic_scene);
         }
+        span_dynamic.record("entity_count", dynamic_world.entities().len());
         span_dynamic.exit();
         Self {
             static_world,
-            dynamic_world,
+            dynamic_layers: HashMap::from([(LayerId::BASE, dynamic_world)]),
+            layer_stack: Vec::new(),
+            next_layer: LayerId(1),
+            transferred: EntityHashMap::default(),
+            shadowed: HashMap::new(),
+            resource_filter: SceneFilter::allow_all().deny::<AppTypeRegistry>(),
+            transfer_events: Vec::new(),
+            frozen: false,
+            strict_transfer: false,
+            transfer_skips: HashSet::new(),
+            preserved_resources: HashMap::new(),
+            indices: HashMap::new(),
+            on_transfer: None,
+            transfer_filter: None,
+            static_checkpoint: None,
         }
     }
+    /// Creates a `DataWorlds` resource from optional RON files on the [IoTaskPool], so loading does not block the calling thread.
+    /// `type_registry` should have registered all components that will be stored in the data worlds.
+    ///
+    /// The returned task can be polled or awaited and resolves to the loaded data.
+    pub fn from_scenes_async(
+        type_registry: &AppTypeRegistry,
+        static_path: Option<PathBuf>,
+        dynamic_path: Option<PathBuf>,
+    ) -> Task<Result<Self, LoadError>> {
+        let type_registry = type_registry.clone();
+        IoTaskPool::get_or_init(TaskPool::new).spawn(async move {
+            let mut data = Self::from_scenes(&type_registry, None, None);
+            if let Some(path) = static_path {
+                data.load_static_from_file(&type_registry, path)?;
+            }
+            if let Some(path) = dynamic_path {
+                data.load_dynamic_from_file(&type_registry, path)?;
+            }
+            Ok(data)
+        })
+    }
+    /// Creates a [builder](DataWorldsBuilder) to configure a new `DataWorlds` resource.
+    #[inline]
+    pub fn builder() -> DataWorldsBuilder {
+        DataWorldsBuilder::default()
+    }
     /// Use a one-time system to modify static data.
     ///
     /// This should only be used for initial setup as data in the static world should be immutable during runtime.
@@ -63,224 +1029,5197 @@ impl DataWorlds {
     ) -> Out {
         self.static_world.run_system_once(system)
     }
-    /// Reload only the dynamic data from a scene.
-    /// All changes made since the last load will be lost.
-    #[inline]
-    pub fn reload_dynamic_data(&mut self, dynamic_scene: DynamicSceneBundle) {
-        let span = trace_span!("create_dynamic_data_world").entered();
-        let mut dynamic_world = World::new();
-        dynamic_world.insert_resource(
-            self.dynamic_world
-                .remove_resource::<AppTypeRegistry>()
-                .expect("Resource should have been added in constructor"),
+    /// Records the current state of the static world, so later mutations can be detected using [`is_static_mutated`](DataWorlds::is_static_mutated).
+    /// This should be called once initial setup is done.
+    pub fn checkpoint_static(&mut self) {
+        let tick = self.static_world.increment_change_tick();
+        self.static_checkpoint = Some((static_checksum(&self.static_world), tick));
+    }
+    /// Returns `true` if static data was spawned, despawned or had components added, removed or changed since the last [checkpoint](DataWorlds::checkpoint_static).
+    /// Returns `false` when no checkpoint was recorded.
+    pub fn is_static_mutated(&self) -> bool {
+        let Some((checksum, tick)) = self.static_checkpoint else {
+            return false;
+        };
+        let this_run = self.static_world.read_change_tick();
+        checksum != static_checksum(&self.static_world)
+            || self.static_world.iter_entities().any(|entity_ref| {
+                entity_ref.archetype().components().any(|component_id| {
+                    entity_ref
+                        .get_change_ticks_by_id(component_id)
+                        .is_some_and(|ticks| ticks.is_changed(tick, this_run))
+                })
+            })
+    }
+    /// Checks that static data was not mutated since the last [checkpoint](DataWorlds::checkpoint_static).
+    ///
+    /// # Panics
+    /// In debug builds, this will panic if [`is_static_mutated`](DataWorlds::is_static_mutated) returns `true`.
+    #[inline]
+    pub fn assert_static_unchanged(&self) {
+        debug_assert!(
+            !self.is_static_mutated(),
+            "static data was mutated after the checkpoint"
         );
+    }
+    /// Use a one-time system to access the active layer.
+    ///
+    /// This is the runtime counterpart to [`modify_static_data`](DataWorlds::modify_static_data), the system has full [Query] access to dynamic data.
+    #[inline(always)]
+    pub fn run_dynamic_system_once<Out, Marker>(
+        &mut self,
+        system: impl IntoSystem<(), Out, Marker>,
+    ) -> Out {
+        self.dynamic_world_mut().run_system_once(system)
+    }
+    /// Spawns each of `bundles` as new static data and returns references to them.
+    ///
+    /// This should only be used for initial setup as data in the static world should be immutable during runtime.
+    pub fn extend_static<I: IntoIterator<Item = B>, B: Bundle>(
+        &mut self,
+        bundles: I,
+    ) -> Vec<DataRef> {
+        bundles
+            .into_iter()
+            .map(|bundle| DataRef::Static(self.static_world.spawn(bundle).id()))
+            .collect()
+    }
+    /// Spawns `bundles` as new static data and returns references to them in the same order,
+    /// so references between the spawned data can be set up using their index.
+    ///
+    /// This should only be used for initial setup as data in the static world should be immutable during runtime.
+    pub fn spawn_static_batch<B: Bundle>(&mut self, bundles: Vec<B>) -> Vec<DataRef> {
+        self.static_world
+            .spawn_batch(bundles)
+            .map(DataRef::Static)
+            .collect()
+    }
+    /// Spawns new data into the active layer.
+    ///
+    /// This is the runtime counterpart to [`modify_static_data`](DataWorlds::modify_static_data).
+    #[inline]
+    pub fn spawn_dynamic<B: Bundle>(&mut self, bundle: B) -> DataRef {
+        let layer = self.active_layer();
+        DataRef::Dynamic(layer, self.dynamic_world_mut().spawn(bundle).id())
+    }
+    /// Reload only the dynamic data of the active layer from a scene.
+    /// All changes made to this layer since the last load will be lost.
+    #[inline]
+    pub fn reload_dynamic_data(&mut self, dynamic_scene: DynamicSceneBundle) {
+        let span = trace_span!("create_dynamic_data_world", entity_count = field::Empty).entered();
+        let mut dynamic_world = self.empty_dynamic_world();
         dynamic_world.spawn(dynamic_scene);
+        span.record("entity_count", dynamic_world.entities().len());
+        span.exit();
+        self.install_dynamic_world(dynamic_world);
+    }
+    /// Removes all dynamic data of the active layer.
+    /// Static data that was transferred into the active layer will be read from a lower layer or the static world again.
+    ///
+    /// The type registry and [preserved resources](DataWorlds::preserve_resource) are kept.
+    #[inline]
+    pub fn clear_dynamic(&mut self) {
+        let dynamic_world = self.empty_dynamic_world();
+        self.install_dynamic_world(dynamic_world);
+    }
+    /// Creates an empty world containing only the type registry, to replace the active layer using [`install_dynamic_world`](DataWorlds::install_dynamic_world).
+    fn empty_dynamic_world(&self) -> World {
+        let mut dynamic_world = World::new();
+        dynamic_world.insert_resource(self.type_registry().clone());
+        dynamic_world
+    }
+    /// Replaces the world of the active layer with `dynamic_world`.
+    /// Preserved resources that `dynamic_world` does not contain are moved over from the previous world.
+    /// Static data that was transferred into the active layer will be read from a lower layer or the static world again.
+    fn install_dynamic_world(&mut self, mut dynamic_world: World) {
+        let layer = self.active_layer();
+        let active_world = self
+            .dynamic_layers
+            .get_mut(&layer)
+            .expect("Active layer should always exist");
+        for move_resource in self.preserved_resources.values() {
+            move_resource(active_world, &mut dynamic_world);
+        }
+        *active_world = dynamic_world;
+        self.forget_transfers(layer);
+    }
+    /// Replace the dynamic data of the active layer with data deserialized from RON, using the type registry of the data worlds.
+    /// All changes made to this layer since the last load will be lost.
+    ///
+    /// This is the inverse of [`serialize_dynamic_ron`](DataWorlds::serialize_dynamic_ron).
+    /// [Preserved resources](DataWorlds::preserve_resource) not contained in `ron` are kept.
+    pub fn reload_dynamic_from_ron(&mut self, ron: &str) -> Result<(), LoadError> {
+        let span = trace_span!("load_dynamic_data_world").entered();
+        let type_registry = self.type_registry().clone();
+        let scene = scene_from_ron(&type_registry, ron)?;
+        self.replace_dynamic_scene(&scene)?;
+        span.exit();
+        Ok(())
+    }
+    /// Replace the dynamic data of the active layer with data deserialized from RON, using the type registry of the data worlds.
+    /// All changes made to this layer since the last load will be lost.
+    ///
+    /// The [version](DataVersion) of the data is detected before spawning and matching `migrations` are applied in order until no migration is left,
+    /// the loaded data will contain the resulting version if [DataVersion] is registered.
+    pub fn load_dynamic_from_ron_with_migrations(
+        &mut self,
+        ron: &str,
+        migrations: &[Box<dyn SceneMigration>],
+    ) -> Result<(), LoadError> {
+        let span = trace_span!("load_dynamic_data_world").entered();
+        let type_registry = self.type_registry().clone();
+        let mut scene = scene_from_ron(&type_registry, ron)?;
+        let position = scene.resources.iter().position(|resource| {
+            resource
+                .get_represented_type_info()
+                .is_some_and(|info| info.type_id() == TypeId::of::<DataVersion>())
+        });
+        let mut version = position
+            .and_then(|index| DataVersion::from_reflect(scene.resources[index].as_ref()))
+            .unwrap_or_default();
+        while let Some(migration) = migrations
+            .iter()
+            .find(|migration| migration.from_version() == version.0)
+        {
+            migration.apply(&mut scene);
+            version.0 += 1;
+        }
+        match position {
+            Some(index) => scene.resources[index] = Box::new(version),
+            None if type_registry
+                .read()
+                .get(TypeId::of::<DataVersion>())
+                .is_some() =>
+            {
+                scene.resources.push(Box::new(version))
+            }
+            None => {}
+        }
+        self.replace_dynamic_scene(&scene)?;
+        span.exit();
+        Ok(())
+    }
+    /// Replace the dynamic data of the active layer with data deserialized from RON, using the type registry of the data worlds.
+    /// All changes made to this layer since the last load will be lost.
+    ///
+    /// Before deserializing, all components and resources in `ron` are compared against the registered types.
+    /// Returns [`LoadError::SchemaMismatch`] listing all unregistered types and unknown or missing struct fields,
+    /// in which case the active layer is left unchanged.
+    pub fn load_dynamic_strict(&mut self, ron: &str) -> Result<(), LoadError> {
+        let span = trace_span!("load_dynamic_data_world").entered();
+        let type_registry = self.type_registry().clone();
+        let mismatches = check_scene_schema(&type_registry, ron::from_str(ron)?);
+        if !mismatches.is_empty() {
+            return Err(LoadError::SchemaMismatch(mismatches));
+        }
+        let scene = scene_from_ron(&type_registry, ron)?;
+        self.replace_dynamic_scene(&scene)?;
+        span.exit();
+        Ok(())
+    }
+    /// Serialized dynamic data of the active layer into RON format, including which static data was transferred into it.
+    /// Unlike [`serialize_dynamic_ron`](DataWorlds::serialize_dynamic_ron) this allows restoring the forwarding of static references using [`load_session`](DataWorlds::load_session).
+    pub fn serialize_session(&self) -> Result<String, RonError> {
+        let layer = self.active_layer();
+        let session = Session {
+            scene: self.serialize_dynamic_ron()?,
+            transferred: self
+                .shadowed_entities()
+                .filter(|(_, target)| matches!(target, DataRef::Dynamic(target_layer, _) if *target_layer == layer))
+                .collect(),
+        };
+        ron::ser::to_string_pretty(&session, PrettyConfig::default())
+    }
+    /// Replace the dynamic data of the active layer with a session produced by [`serialize_session`](DataWorlds::serialize_session),
+    /// using the type registry of the data worlds. All changes made to this layer since the last load will be lost.
+    ///
+    /// Entity ids recorded in the session are kept where possible, static data recorded as transferred is forwarded to its loaded copy again.
+    /// The static data should be the same as when the session was serialized.
+    /// [Preserved resources](DataWorlds::preserve_resource) not contained in the session are kept.
+    pub fn load_session(&mut self, ron: &str) -> Result<(), LoadError> {
+        let span = trace_span!("load_dynamic_data_world").entered();
+        let session = ron::from_str::<Session>(ron)?;
+        let type_registry = self.type_registry().clone();
+        let scene = scene_from_ron(&type_registry, &session.scene)?;
+        let mut dynamic_world = self.empty_dynamic_world();
+        let entity_map = write_scene_preserving_ids(&mut dynamic_world, &scene)?;
+        self.install_dynamic_world(dynamic_world);
+        let layer = self.active_layer();
+        for (source, target) in session.transferred {
+            let (DataRef::Static(source), DataRef::Dynamic(_, target)) = (source, target) else {
+                continue;
+            };
+            if self.static_world.get_entity(source).is_none() {
+                continue;
+            }
+            let target = entity_map.get(&target).copied().unwrap_or(target);
+            self.insert_transfer(source, (layer, target));
+        }
         span.exit();
-        self.dynamic_world = dynamic_world;
+        Ok(())
+    }
+    /// Returns the resource `R` of the active layer.
+    #[inline]
+    pub fn dynamic_resource<R: Resource>(&self) -> Option<&R> {
+        self.dynamic_world().get_resource::<R>()
+    }
+    /// Inserts the resource `R` into the active layer, replacing any previous value.
+    ///
+    /// The type registry can not be replaced this way, use [`register`](DataWorlds::register) to add types instead.
+    #[inline]
+    pub fn insert_dynamic_resource<R: Resource>(&mut self, resource: R) {
+        if TypeId::of::<R>() == TypeId::of::<AppTypeRegistry>() {
+            warn!("tried to replace the type registry of the dynamic world");
+            return;
+        }
+        self.dynamic_world_mut().insert_resource(resource);
+    }
+    /// Keeps the resource `R` of the active layer when reloading it, e.g. using [`reload_dynamic_data`](DataWorlds::reload_dynamic_data),
+    /// or clearing it using [`clear_dynamic`](DataWorlds::clear_dynamic).
+    /// When the loaded data contains the resource itself, the loaded value is used instead.
+    #[inline]
+    pub fn preserve_resource<R: Resource>(&mut self) {
+        self.preserved_resources
+            .insert(TypeId::of::<R>(), |source, target| {
+                if target.contains_resource::<R>() {
+                    return;
+                }
+                if let Some(resource) = source.remove_resource::<R>() {
+                    target.insert_resource(resource);
+                }
+            });
     }
     /// Serialized static data into RON format.
     /// This should only be nessesary for first time setup, as static data is immutable.
     #[inline]
     pub fn serialize_static_ron(&self) -> Result<String, RonError> {
         let span = trace_span!("serialize_static_data_world").entered();
-        let scene = DynamicScene::from_world(&self.static_world);
+        let scene = scene_from_world(&self.static_world, &self.resource_filter);
         let type_registry = self.static_world.resource::<AppTypeRegistry>();
         let result = scene.serialize_ron(type_registry);
         span.exit();
         result
     }
-    /// Serialized dynamic data into RON format.
+    /// Serialized dynamic data of the active layer into RON format.
     #[inline]
     pub fn serialize_dynamic_ron(&self) -> Result<String, RonError> {
         let span = trace_span!("serialize_dynamic_data_world").entered();
-        let scene = DynamicScene::from_world(&self.dynamic_world);
-        let type_registry = self.dynamic_world.resource::<AppTypeRegistry>();
+        let scene = scene_from_world(self.dynamic_world(), &self.resource_filter);
+        let type_registry = self.dynamic_world().resource::<AppTypeRegistry>();
         let result = scene.serialize_ron(type_registry);
         span.exit();
         result
     }
-    /// Returns a reference to the data pointed to by `ptr`, returns [`None`] when the reference is [`Null`](DataRef::Null) or the entity does not exist.
+    /// Serialized dynamic data of the active layer into RON format, formatted using `config`.
+    pub fn serialize_dynamic_ron_pretty(&self, config: PrettyConfig) -> Result<String, RonError> {
+        let span = trace_span!("serialize_dynamic_data_world").entered();
+        let scene = scene_from_world(self.dynamic_world(), &self.resource_filter);
+        let type_registry = self.dynamic_world().resource::<AppTypeRegistry>();
+        let result =
+            ron::ser::to_string_pretty(&SceneSerializer::new(&scene, type_registry), config);
+        span.exit();
+        result
+    }
+    /// Excludes the resource `R` from serialization and [snapshots](DataWorlds::snapshot).
+    /// The [AppTypeRegistry] is always excluded.
     #[inline]
-    pub fn get(&self, ptr: DataRef) -> Option<EntityRef> {
-        match ptr {
-            DataRef::Static(entity) => self.static_world.get_entity(entity),
-            DataRef::Dynamic(entity) => self.dynamic_world.get_entity(entity),
-            DataRef::Null => None,
+    pub fn skip_resource<R: Resource>(&mut self) {
+        self.resource_filter = std::mem::take(&mut self.resource_filter).deny::<R>();
+    }
+    /// Serialized dynamic data of the active layer into RON format and checks that the result can be deserialized again.
+    /// The returned error names the type that could not be serialized or deserialized.
+    pub fn serialize_dynamic_ron_checked(&self) -> Result<String, SerCheckError> {
+        let ron = self.serialize_dynamic_ron()?;
+        let span = trace_span!("check_serialized_dynamic_data_world").entered();
+        scene_from_ron(self.type_registry(), &ron)?;
+        span.exit();
+        Ok(ron)
+    }
+    /// Serialized the dynamic data of the active layer pointed to by `refs` into RON format, resources are not included.
+    /// References that do not point to data in the active layer are ignored.
+    pub fn serialize_dynamic_subset(&self, refs: &[DataRef]) -> Result<String, RonError> {
+        let span = trace_span!("serialize_dynamic_data_world").entered();
+        let active_layer = self.active_layer();
+        let scene = DynamicSceneBuilder::from_world(self.dynamic_world())
+            .extract_entities(refs.iter().filter_map(|&ptr| match self.resolve(ptr) {
+                DataRef::Dynamic(layer, entity) if layer == active_layer => Some(entity),
+                _ => None,
+            }))
+            .build();
+        let type_registry = self.dynamic_world().resource::<AppTypeRegistry>();
+        let result = scene.serialize_ron(type_registry);
+        span.exit();
+        result
+    }
+    /// Save static data to a file in RON format.
+    /// This should only be nessesary for first time setup, as static data is immutable.
+    #[inline]
+    pub fn save_static_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let ron = self.serialize_static_ron().map_err(io::Error::other)?;
+        fs::write(path, ron)
+    }
+    /// Save dynamic data of the active layer to a file in RON format.
+    #[inline]
+    pub fn save_dynamic_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let ron = self.serialize_dynamic_ron().map_err(io::Error::other)?;
+        fs::write(path, ron)
+    }
+    /// Save dynamic data of the active layer to a file in compressed RON format.
+    /// `level` is the compression level between 0 (no compression) and 9 (best compression).
+    ///
+    /// The file starts with a header containing the format version, so incompatible files can be detected when loading.
+    pub fn save_dynamic_compressed(&self, path: impl AsRef<Path>, level: u32) -> io::Result<()> {
+        let ron = self.serialize_dynamic_ron().map_err(io::Error::other)?;
+        let mut bytes = Vec::from(COMPRESSED_MAGIC);
+        bytes.extend(COMPRESSED_VERSION.to_le_bytes());
+        let mut encoder = DeflateEncoder::new(bytes, Compression::new(level));
+        encoder.write_all(ron.as_bytes())?;
+        fs::write(path, encoder.finish()?)
+    }
+    /// Replace the dynamic data of the active layer with data loaded from a file produced by [`save_dynamic_compressed`](DataWorlds::save_dynamic_compressed).
+    /// All changes made to this layer since the last load will be lost.
+    ///
+    /// `type_registry` is used to deserialize the file and should contain all components stored in it.
+    pub fn load_dynamic_compressed(
+        &mut self,
+        type_registry: &AppTypeRegistry,
+        path: impl AsRef<Path>,
+    ) -> Result<(), LoadError> {
+        let span = trace_span!("load_dynamic_data_world").entered();
+        let bytes = fs::read(path)?;
+        let (version, bytes) = bytes
+            .strip_prefix(&COMPRESSED_MAGIC)
+            .and_then(|bytes| bytes.split_first_chunk())
+            .ok_or(LoadError::InvalidHeader)?;
+        let version = u16::from_le_bytes(*version);
+        if version != COMPRESSED_VERSION {
+            return Err(LoadError::UnsupportedVersion(version));
         }
+        let mut ron = String::new();
+        DeflateDecoder::new(bytes).read_to_string(&mut ron)?;
+        let scene = scene_from_ron(type_registry, &ron)?;
+        self.replace_dynamic_scene(&scene)?;
+        span.exit();
+        Ok(())
     }
-    /// Returns a reference to the data pointed to by `ptr`.
+    /// Replace the dynamic data of the active layer with data loaded from a RON file.
+    /// All changes made to this layer since the last load will be lost.
     ///
-    /// # Panics
-    /// This will panic if the reference is [`Null`](DataRef::Null) or the entity does not exits.
+    /// `type_registry` is used to deserialize the file and should contain all components stored in it.
+    pub fn load_dynamic_from_file(
+        &mut self,
+        type_registry: &AppTypeRegistry,
+        path: impl AsRef<Path>,
+    ) -> Result<(), LoadError> {
+        let span = trace_span!("load_dynamic_data_world").entered();
+        let scene = scene_from_ron(type_registry, &fs::read_to_string(path)?)?;
+        self.replace_dynamic_scene(&scene)?;
+        span.exit();
+        Ok(())
+    }
+    /// Replace the dynamic data of the active layer with data loaded from a RON file, keeping the entity ids recorded in the file.
+    /// This way [dynamic references](DataRef::Dynamic) stored outside of the data worlds stay valid.
+    /// All changes made to this layer since the last load will be lost.
+    ///
+    /// Returns a map from the ids in the file to the ids of the loaded data, which can be used to fix up stored references in case an id could not be kept.
+    /// `type_registry` is used to deserialize the file and should contain all components stored in it.
+    pub fn load_dynamic_from_file_preserving_ids(
+        &mut self,
+        type_registry: &AppTypeRegistry,
+        path: impl AsRef<Path>,
+    ) -> Result<EntityHashMap<Entity>, LoadError> {
+        let span = trace_span!("load_dynamic_data_world").entered();
+        let scene = scene_from_ron(type_registry, &fs::read_to_string(path)?)?;
+        let mut dynamic_world = self.empty_dynamic_world();
+        let entity_map = write_scene_preserving_ids(&mut dynamic_world, &scene)?;
+        self.install_dynamic_world(dynamic_world);
+        span.exit();
+        Ok(entity_map)
+    }
+    /// Replace the static data with data loaded from a RON file.
+    /// This should only be used for initial setup, as static data should be immutable during runtime.
+    ///
+    /// Transferred data will no longer be forwarded to its dynamic copy, as static references are invalidated.
+    /// `type_registry` is used to deserialize the file and should contain all components stored in it.
+    pub fn load_static_from_file(
+        &mut self,
+        type_registry: &AppTypeRegistry,
+        path: impl AsRef<Path>,
+    ) -> Result<(), LoadError> {
+        let span = trace_span!("load_static_data_world").entered();
+        let scene = scene_from_ron(type_registry, &fs::read_to_string(path)?)?;
+        let mut static_world = World::new();
+        static_world.insert_resource(type_registry.clone());
+        scene.write_to_world(&mut static_world, &mut EntityHashMap::default())?;
+        self.static_world = static_world;
+        self.transferred.clear();
+        self.shadowed.clear();
+        span.exit();
+        Ok(())
+    }
+    /// Serialized static data into JSON format, entities are ordered by their id.
+    /// This should only be nessesary for first time setup, as static data is immutable.
     #[inline]
-    pub fn entity(&self, ptr: DataRef) -> EntityRef {
-        match ptr {
-            DataRef::Static(entity) => self.static_world.entity(entity),
-            DataRef::Dynamic(entity) => self.dynamic_world.entity(entity),
-            DataRef::Null => panic!("Tried to access null reference"),
+    pub fn serialize_static_json(&self) -> Result<String, JsonError> {
+        let span = trace_span!("serialize_static_data_world").entered();
+        let scene = scene_from_world(&self.static_world, &self.resource_filter);
+        let type_registry = self.static_world.resource::<AppTypeRegistry>();
+        let result = serde_json::to_string_pretty(&SceneSerializer::new(&scene, type_registry));
+        span.exit();
+        result
+    }
+    /// Serialized static data using `format`, entities are ordered by their id.
+    /// This should only be nessesary for first time setup, as static data is immutable.
+    pub fn serialize_static<F: DataFormat>(&self, format: &F) -> Result<Vec<u8>, FormatError> {
+        let span = trace_span!("serialize_static_data_world").entered();
+        let scene = scene_from_world(&self.static_world, &self.resource_filter);
+        let result = format.serialize(&scene, self.type_registry());
+        span.exit();
+        result
+    }
+    /// Serialized dynamic data of the active layer using `format`, entities are ordered by their id.
+    pub fn serialize_dynamic<F: DataFormat>(&self, format: &F) -> Result<Vec<u8>, FormatError> {
+        let span = trace_span!("serialize_dynamic_data_world").entered();
+        let scene = scene_from_world(self.dynamic_world(), &self.resource_filter);
+        let type_registry = self.dynamic_world().resource::<AppTypeRegistry>();
+        let result = format.serialize(&scene, type_registry);
+        span.exit();
+        result
+    }
+    /// Replace the dynamic data of the active layer with data deserialized using `format`.
+    /// All changes made to this layer since the last load will be lost.
+    ///
+    /// `type_registry` is used to deserialize the data and should contain all components stored in it.
+    pub fn load_dynamic<F: DataFormat>(
+        &mut self,
+        format: &F,
+        type_registry: &AppTypeRegistry,
+        bytes: &[u8],
+    ) -> Result<(), LoadError> {
+        let span = trace_span!("load_dynamic_data_world").entered();
+        let scene = format.deserialize(bytes, type_registry)?;
+        self.replace_dynamic_scene(&scene)?;
+        span.exit();
+        Ok(())
+    }
+    /// Serialized dynamic data of the active layer into JSON format, entities are ordered by their id.
+    #[inline]
+    pub fn serialize_dynamic_json(&self) -> Result<String, JsonError> {
+        let span = trace_span!("serialize_dynamic_data_world").entered();
+        let scene = scene_from_world(self.dynamic_world(), &self.resource_filter);
+        let type_registry = self.dynamic_world().resource::<AppTypeRegistry>();
+        let result = serde_json::to_string_pretty(&SceneSerializer::new(&scene, type_registry));
+        span.exit();
+        result
+    }
+    /// Replace the dynamic data of the active layer with data loaded from JSON.
+    /// All changes made to this layer since the last load will be lost.
+    ///
+    /// `type_registry` is used to deserialize the data and should contain all components stored in it.
+    pub fn load_dynamic_from_json(
+        &mut self,
+        type_registry: &AppTypeRegistry,
+        json: &str,
+    ) -> Result<(), LoadError> {
+        let span = trace_span!("load_dynamic_data_world").entered();
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let scene = SceneDeserializer {
+            type_registry: &type_registry.read(),
         }
+        .deserialize(&mut deserializer)?;
+        self.replace_dynamic_scene(&scene)?;
+        span.exit();
+        Ok(())
     }
-    /// Returns a mutable reference to the data pointed to by `ptr`, returns [`None`] when the reference is [`Null`](DataRef::Null) or the entity does not exist.
-    /// Static data will be cloned into the dynamic world
+    /// Serialized dynamic data of the active layer into a compact binary format, entities are ordered by their id.
+    /// The data starts with a header containing the format version, so incompatible data can be detected when loading.
     #[inline]
-    pub fn get_mut(&mut self, ptr: DataRef) -> DataMut {
-        match ptr {
-            DataRef::Static(entity) => {
-                let Some(entity) = self.transfer(entity) else {
-                    return DataMut::Missing;
-                };
-                let Some(ptr) = self.dynamic_world.get_entity_mut(entity) else {
-                    return DataMut::Missing;
-                };
-                DataMut::Moved(ptr, DataRef::Dynamic(entity))
-            }
-            DataRef::Dynamic(entity) => {
-                let Some(ptr) = self.dynamic_world.get_entity_mut(entity) else {
-                    return DataMut::Missing;
-                };
-                DataMut::Found(ptr)
-            }
-            DataRef::Null => DataMut::Missing,
+    pub fn serialize_dynamic_bytes(&self) -> Result<Vec<u8>, SerError> {
+        let span = trace_span!("serialize_dynamic_data_world").entered();
+        let scene = scene_from_world(self.dynamic_world(), &self.resource_filter);
+        let type_registry = self.dynamic_world().resource::<AppTypeRegistry>();
+        let mut bytes = Vec::from(BINARY_MAGIC);
+        bytes.extend(BINARY_VERSION.to_le_bytes());
+        let result = bincode::DefaultOptions::new()
+            .serialize_into(&mut bytes, &SceneSerializer::new(&scene, type_registry));
+        span.exit();
+        result?;
+        Ok(bytes)
+    }
+    /// Replace the dynamic data of the active layer with data loaded from the binary format produced by [`serialize_dynamic_bytes`](DataWorlds::serialize_dynamic_bytes).
+    /// All changes made to this layer since the last load will be lost.
+    ///
+    /// `type_registry` is used to deserialize the data and should contain all components stored in it.
+    pub fn load_dynamic_from_bytes(
+        &mut self,
+        type_registry: &AppTypeRegistry,
+        bytes: &[u8],
+    ) -> Result<(), LoadError> {
+        let span = trace_span!("load_dynamic_data_world").entered();
+        let (version, bytes) = bytes
+            .strip_prefix(&BINARY_MAGIC)
+            .and_then(|bytes| bytes.split_first_chunk())
+            .ok_or(LoadError::InvalidHeader)?;
+        let version = u16::from_le_bytes(*version);
+        if version != BINARY_VERSION {
+            return Err(LoadError::UnsupportedVersion(version));
         }
+        let scene = bincode::DefaultOptions::new().deserialize_seed(
+            SceneDeserializer {
+                type_registry: &type_registry.read(),
+            },
+            bytes,
+        )?;
+        self.replace_dynamic_scene(&scene)?;
+        span.exit();
+        Ok(())
     }
-    /// Returns a mutable reference to the data pointed to by `ptr`.
+    /// Adds the data of a scene to the static world without removing existing data and returns references to the new data.
+    /// This should only be used for initial setup, e.g. to assemble static data from multiple files, as static data should be immutable during runtime.
     ///
-    /// # Panics
-    /// This will panic if the reference is [`Null`](DataRef::Null) or the entity does not exits.
-    #[inline]
-    pub fn entity_mut(&mut self, ptr: DataRef) -> DataMut {
-        match ptr {
-            DataRef::Static(entity) => {
-                let Some(entity) = self.transfer(entity) else {
-                    return DataMut::Missing;
-                };
-                let Some(ptr) = self.dynamic_world.get_entity_mut(entity) else {
-                    return DataMut::Missing;
-                };
-                DataMut::Moved(ptr, DataRef::Dynamic(entity))
-            }
-            DataRef::Dynamic(entity) => {
-                let Some(ptr) = self.dynamic_world.get_entity_mut(entity) else {
-                    return DataMut::Missing;
-                };
-                DataMut::Found(ptr)
-            }
-            DataRef::Null => panic!("Tried to access null reference"),
+    /// All entities of the scene are spawned with fresh ids.
+    /// Static references between entities of the scene are rewritten to point to their new locations.
+    pub fn merge_static_scene(&mut self, scene: DynamicScene) -> Result<Vec<DataRef>, MergeError> {
+        let span = trace_span!("merge_static_data_world").entered();
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(&mut self.static_world, &mut entity_map)?;
+        for &entity in entity_map.values() {
+            map_entity_refs(&mut self.static_world, entity, &mut |ptr| match ptr {
+                DataRef::Static(entity) => entity_map
+                    .get(&entity)
+                    .map_or(ptr, |&target| DataRef::Static(target)),
+                _ => ptr,
+            });
+        }
+        span.exit();
+        Ok(scene
+            .entities
+            .iter()
+            .map(|entity| DataRef::Static(entity_map[&entity.entity]))
+            .collect())
+    }
+    /// Adds the data of a scene to the active layer without removing existing data and returns references to the new data.
+    ///
+    /// All entities of the scene are spawned with fresh ids.
+    /// Dynamic references between entities of the scene are rewritten to point to their new locations.
+    pub fn merge_dynamic_scene(&mut self, scene: DynamicScene) -> Result<Vec<DataRef>, MergeError> {
+        let span = trace_span!("merge_dynamic_data_world").entered();
+        let layer = self.active_layer();
+        let dynamic_world = self.dynamic_world_mut();
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(dynamic_world, &mut entity_map)?;
+        for &entity in entity_map.values() {
+            map_entity_refs(dynamic_world, entity, &mut |ptr| match ptr {
+                DataRef::Dynamic(_, entity) => entity_map
+                    .get(&entity)
+                    .map_or(ptr, |&target| DataRef::Dynamic(layer, target)),
+                _ => ptr,
+            });
         }
+        span.exit();
+        Ok(scene
+            .entities
+            .iter()
+            .map(|entity| DataRef::Dynamic(layer, entity_map[&entity.entity]))
+            .collect())
     }
+    /// Replaces the active layer with a new world containing `scene`, keeping preserved resources.
+    fn replace_dynamic_scene(&mut self, scene: &DynamicScene) -> Result<(), SceneSpawnError> {
+        let mut dynamic_world = self.empty_dynamic_world();
+        scene.write_to_world(&mut dynamic_world, &mut EntityHashMap::default())?;
+        self.install_dynamic_world(dynamic_world);
+        Ok(())
+    }
+    /// Returns the layer that is currently on top of the layer stack.
+    /// Data transferred from the static world will be placed in this layer.
     #[inline]
-    fn transfer(&mut self, entity: Entity) -> Option<Entity> {
-        trace!("transfer entity to dynamic world");
-        let source_ref = self.static_world.get_entity(entity)?;
-        let target = self.dynamic_world.spawn_empty().id();
-        let components = self.static_world.components();
-        // SAFETY: constructor guaranties that a `AppTypeRegistry` is added.
-        let registry = self.static_world.resource::<AppTypeRegistry>();
-        let registry_guard = registry.read();
-        for component_id in source_ref.archetype().components() {
-            let type_id = components
-                .get_info(component_id)
-                .expect("Component should implement Reflect")
-                .type_id()
-                .expect("Component should be a rust type");
-            registry_guard
-                .get(type_id)
-                .expect("type should be registered")
-                .data::<ReflectComponent>()
-                .expect("Data should be added by Reflect derive")
-                .copy(
-                    &self.static_world,
-                    &mut self.dynamic_world,
-                    entity,
-                    target,
-                    &registry_guard,
-                );
+    pub fn active_layer(&self) -> LayerId {
+        self.layer_stack.last().copied().unwrap_or(LayerId::BASE)
+    }
+    /// Adds a new empty dynamic layer on top of the layer stack and makes it the active layer.
+    #[inline]
+    pub fn push_layer(&mut self) -> LayerId {
+        let layer = self.next_layer;
+        self.next_layer = LayerId(layer.0 + 1);
+        let mut world = World::new();
+        world.insert_resource(self.static_world.resource::<AppTypeRegistry>().clone());
+        self.dynamic_layers.insert(layer, world);
+        self.layer_stack.push(layer);
+        layer
+    }
+    /// Removes the active layer from the layer stack and returns its world, the layer below becomes the active layer.
+    /// Static data that was transferred into the removed layer will be read from a lower layer or the static world again.
+    ///
+    /// Returns [`None`] when only the [base layer](LayerId::BASE) is left, as it can not be removed.
+    #[inline]
+    pub fn pop_layer(&mut self) -> Option<World> {
+        let layer = self.layer_stack.pop()?;
+        self.forget_transfers(layer);
+        self.dynamic_layers.remove(&layer)
+    }
+    /// Returns `true` if both data worlds contain equivalent data, ignoring entity ids.
+    /// The static worlds and each dynamic layer are compared separately, entities are matched by their reflected component values.
+    ///
+    /// [DataRef] fields are compared as-is. Resources and transfer bookkeeping are not compared.
+    /// This is intended for tests and compares every pair of entities, so it should not be used on large worlds.
+    pub fn content_eq(&self, other: &DataWorlds) -> bool {
+        if self.layer_stack.len() != other.layer_stack.len() {
+            return false;
         }
-        Some(target)
+        world_content_eq(&self.static_world, &other.static_world)
+            && self
+                .layers()
+                .zip(other.layers())
+                .all(|(layer, other_layer)| {
+                    world_content_eq(
+                        &self.dynamic_layers[&layer],
+                        &other.dynamic_layers[&other_layer],
+                    )
+                })
     }
-}
-
-/// Reference to data stored in [DataWorlds].
-///
-/// # Safety
-/// For data that is static but might be mutabe at a later point all cross references should be `DataRef` instead of plain [Entity] fields,
-/// as those would get invalidated when the data gets transfered to the dynamic world.
-#[derive(Debug, Reflect, Default, Clone, Copy, PartialEq, Eq)]
-#[reflect(Default, PartialEq)]
-pub enum DataRef {
-    /// Null pointer.
-    #[default]
-    Null,
-    /// Data located in the static world.
-    Static(Entity),
-    /// Data located in the dynamic world.
-    Dynamic(Entity),
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[derive(Debug, Clone, Copy, Reflect, Component)]
-    #[reflect(Component)]
-    struct SomeCompoennt {
-        data: i32,
+    /// Returns all layers from the bottom to the top of the layer stack.
+    #[inline]
+    fn layers(&self) -> impl Iterator<Item = LayerId> + '_ {
+        [LayerId::BASE]
+            .into_iter()
+            .chain(self.layer_stack.iter().copied())
     }
-    #[derive(Debug, Clone, Copy, Reflect, Component)]
-    #[reflect(Component)]
-    struct SomeRef {
-        entity: DataRef,
+    /// Returns the static world, e.g. to run custom queries against static data.
+    ///
+    /// Entities in this world may be shadowed by dynamic copies, use [`resolve`](DataWorlds::resolve) to find the current location of data.
+    #[inline]
+    pub fn static_world(&self) -> &World {
+        &self.static_world
     }
-    fn setup_data(world: &mut World) -> DataRef {
-        let a = world.spawn(SomeCompoennt { data: 42 }).id();
-        let b = world
-            .spawn((
-                SomeCompoennt { data: 21 },
-                SomeRef {
-                    entity: DataRef::Static(a),
-                },
-            ))
-            .id();
-        DataRef::Static(b)
+    /// Returns the world of the active layer, e.g. to run custom queries against dynamic data.
+    #[inline]
+    pub fn dynamic_world(&self) -> &World {
+        &self.dynamic_layers[&self.active_layer()]
     }
-
-    #[test]
-    fn test() {
-        let type_registry = AppTypeRegistry::default();
-        {
-            let mut guard = type_registry.write();
-            guard.register::<SomeCompoennt>();
-            guard.register::<SomeRef>();
+    /// Returns the world of the active layer mutably.
+    ///
+    /// Changes made through this are not tracked by `DataWorlds`.
+    /// Despawning transferred entities directly will leave static references pointing to missing data
+    /// and removing the [AppTypeRegistry] resource will break transfers and serialization.
+    ///
+    /// There is intentionally no mutable access to the static world, as static data is assumed to be immutable.
+    /// Use [`modify_static_data`](DataWorlds::modify_static_data) during setup instead.
+    #[inline]
+    pub fn dynamic_world_mut(&mut self) -> &mut World {
+        let layer = self.active_layer();
+        self.dynamic_layers
+            .get_mut(&layer)
+            .expect("Active layer should always exist")
+    }
+    /// Removes the world of the active layer and replaces it with an empty world.
+    /// Static data that was transferred into the active layer will be read from a lower layer or the static world again.
+    ///
+    /// The returned world can later be reinstalled using [`set_dynamic_world`](DataWorlds::set_dynamic_world),
+    /// which will forward references to the static originals to the dynamic copies again.
+    pub fn take_dynamic_world(&mut self) -> World {
+        let mut dynamic_world = World::new();
+        dynamic_world.insert_resource(self.type_registry().clone());
+        self.set_dynamic_world(dynamic_world)
+    }
+    /// Replaces the world of the active layer with `dynamic_world` and returns the previous world.
+    /// Static data that was transferred into the active layer will be read from a lower layer or the static world again.
+    ///
+    /// The transfers are kept with the returned world, so they are restored when it is installed again.
+    /// The type registry is added to `dynamic_world` if it does not contain one.
+    pub fn set_dynamic_world(&mut self, mut dynamic_world: World) -> World {
+        if !dynamic_world.contains_resource::<AppTypeRegistry>() {
+            dynamic_world.insert_resource(self.type_registry().clone());
         }
-        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
-        let root = data.modify_static_data(setup_data);
-        let mut world = World::new();
-        world.insert_resource(type_registry);
-        world.insert_resource(data);
+        let layer = self.active_layer();
+        let detached = self
+            .transferred
+            .iter()
+            .filter(|(_, (target_layer, _))| *target_layer == layer)
+            .map(|(&source, &(_, target))| (source, target))
+            .collect();
+        self.forget_transfers(layer);
+        if let Some(DetachedTransfers(transferred)) =
+            dynamic_world.remove_resource::<DetachedTransfers>()
         {
-            let data = world.resource_ref::<DataWorlds>();
-            let b = data.entity(root);
-            assert_eq!(b.get::<SomeCompoennt>().unwrap().data, 21);
-            let a = data.entity(b.get::<SomeRef>().unwrap().entity);
-            assert_eq!(a.get::<SomeCompoennt>().unwrap().data, 42);
+            for (source, target) in transferred {
+                if dynamic_world.get_entity(target).is_some() {
+                    self.insert_transfer(source, (layer, target));
+                }
+            }
         }
-        {
+        let mut previous = std::mem::replace(self.dynamic_world_mut(), dynamic_world);
+        previous.insert_resource(DetachedTransfers(detached));
+        previous
+    }
+    /// Forgets all transfers into `layer`, data that was transferred into a lower layer before is forwarded to that copy again.
+    fn forget_transfers(&mut self, layer: LayerId) {
+        let sources = self
+            .transferred
+            .iter()
+            .filter(|(_, (target_layer, _))| *target_layer == layer)
+            .map(|(&source, _)| source)
+            .collect::<Vec<_>>();
+        for source in sources {
+            self.remove_transfer(source);
+        }
+        self.shadowed.remove(&layer);
+    }
+    /// Forwards references to the static `source` to `target`.
+    /// A copy in a lower layer is remembered, so it can be restored when the layer of `target` is removed.
+    fn insert_transfer(&mut self, source: Entity, target: (LayerId, Entity)) {
+        if let Some(previous) = self.transferred.insert(source, target) {
+            if previous.0 != target.0 {
+                self.shadowed
+                    .entry(target.0)
+                    .or_default()
+                    .insert(source, previous);
+            }
+        }
+    }
+    /// Stops forwarding references to the static `source` to its current copy, the copy it was hiding is used instead.
+    fn remove_transfer(&mut self, source: Entity) -> Option<(LayerId, Entity)> {
+        let (layer, target) = self.transferred.remove(&source)?;
+        if let Some(previous) = self
+            .shadowed
+            .get_mut(&layer)
+            .and_then(|shadowed| shadowed.remove(&source))
+        {
+            self.transferred.insert(source, previous);
+        }
+        Some((layer, target))
+    }
+    /// Forgets the transferred copy at `location`, wherever it is in the stack of copies of its static original.
+    fn unlink_transfer(&mut self, location: (LayerId, Entity)) {
+        if let Some(source) = self
+            .transferred
+            .iter()
+            .find_map(|(&source, &target)| (target == location).then_some(source))
+        {
+            self.remove_transfer(source);
+            return;
+        }
+        let (layer, _) = location;
+        let hiding = self.shadowed.iter().find_map(|(&upper, shadowed)| {
+            shadowed
+                .iter()
+                .find_map(|(&source, &target)| (target == location).then_some((upper, source)))
+        });
+        if let Some((upper, source)) = hiding {
+            let previous = self
+                .shadowed
+                .get_mut(&layer)
+                .and_then(|shadowed| shadowed.remove(&source));
+            let shadowed = self.shadowed.entry(upper).or_default();
+            match previous {
+                Some(previous) => shadowed.insert(source, previous),
+                None => shadowed.remove(&source),
+            };
+        }
+    }
+    /// Returns the current location of the data pointed to by `ptr`.
+    /// Static references to data that was already transferred to a dynamic layer will be forwarded to the dynamic copy,
+    /// all other references are returned unchanged.
+    #[inline]
+    pub fn resolve(&self, ptr: DataRef) -> DataRef {
+        match ptr {
+            DataRef::Static(entity) => self
+                .transferred
+                .get(&entity)
+                .map_or(ptr, |&(layer, target)| DataRef::Dynamic(layer, target)),
+            _ => ptr,
+        }
+    }
+    /// Returns the current location of the data pointed to by `ptr` like [`resolve`](DataWorlds::resolve),
+    /// but returns [`Null`](DataRef::Null) when the data does not exist (anymore).
+    ///
+    /// This can be used to clear references to [despawned](DataWorlds::despawn) data.
+    #[inline]
+    pub fn get_or_null(&self, ptr: DataRef) -> DataRef {
+        match self.get(ptr) {
+            Some(_) => self.resolve(ptr),
+            None => DataRef::Null,
+        }
+    }
+    /// Returns a reference to the data pointed to by `ptr`, returns [`None`] when the reference is [`Null`](DataRef::Null) or the entity does not exist.
+    #[inline]
+    pub fn get(&self, ptr: DataRef) -> Option<EntityRef<'_>> {
+        match self.resolve(ptr) {
+            DataRef::Static(entity) => self.static_world.get_entity(entity),
+            DataRef::Dynamic(layer, entity) => self.dynamic_layers.get(&layer)?.get_entity(entity),
+            DataRef::Null => None,
+        }
+    }
+    /// Returns a read-only reference to the data pointed to by `ptr` without transferring static data, see [`get`](DataWorlds::get).
+    ///
+    /// This allows cheap reads in a mutable context, static data that was not transferred yet is read directly from the static world.
+    #[inline]
+    pub fn entity_mut_static_readonly(&mut self, ptr: DataRef) -> Option<EntityRef<'_>> {
+        self.get(ptr)
+    }
+    /// Returns references to the data pointed to by each of `ptrs`, see [`get`](DataWorlds::get).
+    #[inline]
+    pub fn get_many<const N: usize>(&self, ptrs: [DataRef; N]) -> [Option<EntityRef<'_>>; N] {
+        ptrs.map(|ptr| self.get(ptr))
+    }
+    /// Returns references to all children of the data pointed to by `ptr`, stored as references in the component `C`.
+    /// Children that can not be resolved are skipped, returns an empty list when the data does not have the component.
+    pub fn children_of<C: Component + AsRef<[DataRef]>>(&self, ptr: DataRef) -> Vec<EntityRef<'_>> {
+        self.get_component::<C>(ptr)
+            .map(|children| {
+                children
+                    .as_ref()
+                    .iter()
+                    .filter_map(|&child| self.get(child))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+    /// Calls `f` for every [DataRef] stored in any component of the data pointed to by `ptr`,
+    /// including references nested in structs, enums and collections.
+    pub fn visit_refs(&self, ptr: DataRef, mut f: impl FnMut(DataRef)) {
+        let Some(world) = self.world_for(self.resolve(ptr)) else {
+            return;
+        };
+        if let Some(entity_ref) = self.get(ptr) {
+            visit_entity_refs(world, entity_ref, &mut f);
+        }
+    }
+    /// Replaces every [DataRef] stored in any component of the data pointed to by `ptr` with the result of `map`,
+    /// including references nested in structs, enums and collections.
+    /// Static data will be cloned into the active dynamic layer first, unless it was already cloned before.
+    pub fn rewrite_refs(&mut self, ptr: DataRef, map: impl Fn(DataRef) -> DataRef) {
+        let Some((layer, entity)) = self.locate_mut(ptr) else {
+            return;
+        };
+        if let Some(dynamic_world) = self.dynamic_layers.get_mut(&layer) {
+            if dynamic_world.get_entity(entity).is_some() {
+                map_entity_refs(dynamic_world, entity, &mut |ptr| map(ptr));
+            }
+        }
+    }
+    /// Follows references starting at `start` and returns the last entity of the chain.
+    ///
+    /// Each hop extracts the next reference using `field` from entities that have a `T` component,
+    /// the chain ends at the first entity without `T`, or after at most `depth` hops.
+    /// Returns [`None`] when any reference along the chain can not be resolved.
+    pub fn resolve_path<T: Component>(
+        &self,
+        start: DataRef,
+        field: impl Fn(&EntityRef) -> DataRef,
+        depth: usize,
+    ) -> Option<EntityRef<'_>> {
+        let mut current = self.get(start)?;
+        for _ in 0..depth {
+            if !current.contains::<T>() {
+                break;
+            }
+            current = self.get(field(&current))?;
+        }
+        Some(current)
+    }
+    /// Returns `true` if the data pointed to by `ptr` exists with the same generation, see [`contains`](DataWorlds::contains).
+    /// Stale references whose id was reused by newer data are never valid.
+    #[inline]
+    pub fn is_valid(&self, ptr: DataRef) -> bool {
+        self.contains(ptr)
+    }
+    /// Returns `true` if `ptr` is stale, because the data it pointed to was despawned and its id was reused by newer data.
+    pub fn is_reused(&self, ptr: DataRef) -> bool {
+        let (world, entity) = match self.resolve(ptr) {
+            DataRef::Static(entity) => (&self.static_world, entity),
+            DataRef::Dynamic(layer, entity) => match self.dynamic_layers.get(&layer) {
+                Some(world) => (world, entity),
+                None => return false,
+            },
+            DataRef::Null => return false,
+        };
+        world
+            .entities()
+            .resolve_from_id(entity.index())
+            .is_some_and(|current| current != entity && world.get_entity(current).is_some())
+    }
+    /// Returns the kind of world the data pointed to by `ptr` is currently stored in, after forwarding transferred data.
+    /// Returns [`None`] when the reference is [`Null`](DataRef::Null) or the entity does not exist.
+    #[inline]
+    pub fn entity_location(&self, ptr: DataRef) -> Option<WorldKind> {
+        if !self.contains(ptr) {
+            return None;
+        }
+        match self.resolve(ptr) {
+            DataRef::Static(_) => Some(WorldKind::Static),
+            DataRef::Dynamic(..) => Some(WorldKind::Dynamic),
+            DataRef::Null => None,
+        }
+    }
+    /// Returns `true` when [`get`](DataWorlds::get) would succeed for `ptr`.
+    #[inline]
+    pub fn contains(&self, ptr: DataRef) -> bool {
+        match self.resolve(ptr) {
+            DataRef::Static(entity) => self.static_world.get_entity(entity).is_some(),
+            DataRef::Dynamic(layer, entity) => self
+                .dynamic_layers
+                .get(&layer)
+                .is_some_and(|world| world.get_entity(entity).is_some()),
+            DataRef::Null => false,
+        }
+    }
+    /// Returns a reference to the data pointed to by `ptr`, returns an error when the reference is [`Null`](DataRef::Null) or the entity does not exist.
+    #[inline]
+    pub fn try_entity(&self, ptr: DataRef) -> Result<EntityRef<'_>, DataError> {
+        match ptr {
+            DataRef::Null => Err(DataError::NullReference),
+            _ => self.get(ptr).ok_or(DataError::Missing(ptr)),
+        }
+    }
+    /// Returns a reference to the data pointed to by `ptr`.
+    ///
+    /// # Panics
+    /// This will panic if the reference is [`Null`](DataRef::Null) or the entity does not exits.
+    #[inline]
+    pub fn entity(&self, ptr: DataRef) -> EntityRef<'_> {
+        self.try_entity(ptr).unwrap_or_else(|err| panic!("{err}"))
+    }
+    /// Returns a reference to the data pointed to by `ptr`, returns `default` when the entity does not exist.
+    /// See [`entity_or_else`](DataWorlds::entity_or_else) for the handling of [`Null`](DataRef::Null) references.
+    #[inline]
+    pub fn entity_or<'a>(&'a self, ptr: DataRef, default: EntityRef<'a>) -> EntityRef<'a> {
+        self.entity_or_else(ptr, || default)
+    }
+    /// Returns a reference to the data pointed to by `ptr`, returns the result of `f` when the entity does not exist.
+    ///
+    /// When the reference is [`Null`](DataRef::Null) an error will be logged and the result of `f` is returned,
+    /// unless the `strict-null` feature is enabled.
+    ///
+    /// # Panics
+    /// With the `strict-null` feature enabled, this will panic if the reference is [`Null`](DataRef::Null).
+    #[inline]
+    pub fn entity_or_else<'a>(
+        &'a self,
+        ptr: DataRef,
+        f: impl FnOnce() -> EntityRef<'a>,
+    ) -> EntityRef<'a> {
+        match self.try_entity(ptr) {
+            Ok(entity) => entity,
+            Err(err @ DataError::NullReference) => {
+                if cfg!(feature = "strict-null") {
+                    panic!("{err}");
+                }
+                error!("{err}");
+                f()
+            }
+            Err(_) => f(),
+        }
+    }
+    /// Returns a mutable reference to the data pointed to by `ptr`, returns [`None`] when the reference is [`Null`](DataRef::Null) or the entity does not exist.
+    /// Static data will be cloned into the active dynamic layer, unless it was already cloned before.
+    #[inline]
+    pub fn get_mut(&mut self, ptr: DataRef) -> DataMut<'_> {
+        self.try_entity_mut(ptr).unwrap_or_else(|err| {
+            if let DataError::Transfer(err) = err {
+                warn!("{err}");
+            }
+            DataMut::Missing
+        })
+    }
+    /// Returns a mutable reference to the data pointed to by `ptr`, see [`get_mut`](DataWorlds::get_mut).
+    /// Unlike [`get_mut`](DataWorlds::get_mut) this does not report whether the data was moved,
+    /// references to static data will still be forwarded to the dynamic copy.
+    #[inline]
+    pub fn entity_world_mut(&mut self, ptr: DataRef) -> Option<EntityWorldMut<'_>> {
+        self.get_mut(ptr).ok()
+    }
+    /// Transfers the static data pointed to by `ptr` into the active dynamic layer, unless it was already transferred before,
+    /// and returns a reference to the dynamic copy.
+    /// Dynamic data is returned unchanged.
+    ///
+    /// Returns an error when the reference is [`Null`](DataRef::Null), the entity does not exist or a component can not be copied.
+    pub fn try_transfer(&mut self, ptr: DataRef) -> Result<DataRef, TransferError> {
+        match ptr {
+            DataRef::Static(entity) => self
+                .transfer(entity)
+                .map(|(layer, entity)| DataRef::Dynamic(layer, entity)),
+            DataRef::Dynamic(layer, entity) => self
+                .dynamic_layers
+                .get(&layer)
+                .and_then(|world| world.get_entity(entity))
+                .map(|_| ptr)
+                .ok_or(TransferError::Missing(entity)),
+            DataRef::Null => Err(TransferError::NullReference),
+        }
+    }
+    /// Returns a mutable reference to the data pointed to by `ptr`, returns an error when the reference is [`Null`](DataRef::Null) or the entity does not exist.
+    /// Static data will be cloned into the active dynamic layer, unless it was already cloned before.
+    ///
+    /// The returned [`DataMut`] is never [`Missing`](DataMut::Missing).
+    #[inline]
+    pub fn try_entity_mut(&mut self, ptr: DataRef) -> Result<DataMut<'_>, DataError> {
+        match ptr {
+            DataRef::Static(entity) => {
+                let (layer, entity) = self.transfer(entity).map_err(|err| match err {
+                    TransferError::Missing(_) => DataError::Missing(ptr),
+                    err => DataError::Transfer(err),
+                })?;
+                let entity_mut = self
+                    .dynamic_layers
+                    .get_mut(&layer)
+                    .and_then(|world| world.get_entity_mut(entity))
+                    .ok_or(DataError::Missing(ptr))?;
+                Ok(DataMut::Moved {
+                    entity: entity_mut,
+                    old: ptr,
+                    new: DataRef::Dynamic(layer, entity),
+                })
+            }
+            DataRef::Dynamic(layer, entity) => self
+                .dynamic_layers
+                .get_mut(&layer)
+                .and_then(|world| world.get_entity_mut(entity))
+                .map(DataMut::Found)
+                .ok_or(DataError::Missing(ptr)),
+            DataRef::Null => Err(DataError::NullReference),
+        }
+    }
+    /// Returns a mutable reference to the data pointed to by `ptr`.
+    /// Static data will be cloned into the active dynamic layer, unless it was already cloned before.
+    ///
+    /// # Panics
+    /// This will panic if the reference is [`Null`](DataRef::Null) or the entity does not exits.
+    #[inline]
+    pub fn entity_mut(&mut self, ptr: DataRef) -> DataMut<'_> {
+        self.try_entity_mut(ptr)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+    /// Returns a reference to the component `T` of the data pointed to by `ptr`.
+    /// Returns [`None`] when the reference is [`Null`](DataRef::Null), the entity does not exist or does not have the component.
+    #[inline]
+    pub fn get_component<T: Component>(&self, ptr: DataRef) -> Option<&T> {
+        self.get(ptr)?.get::<T>()
+    }
+    /// Returns a clone of the component `T` of the data pointed to by `ptr`, see [`get_component`](DataWorlds::get_component).
+    #[inline]
+    pub fn read_component<T: Component + Clone>(&self, ptr: DataRef) -> Option<T> {
+        self.get_component::<T>(ptr).cloned()
+    }
+    /// Returns `true` if the data pointed to by `ptr` has the component `T`.
+    #[inline]
+    pub fn has_component<T: Component>(&self, ptr: DataRef) -> bool {
+        self.get(ptr).is_some_and(|entity| entity.contains::<T>())
+    }
+    /// Returns `true` if the data pointed to by `ptr` has the component registered with the type path `type_path`.
+    /// Returns `false` when the type is not registered in the type registry.
+    pub fn has_component_by_name(&self, ptr: DataRef, type_path: &str) -> bool {
+        let Some(type_id) = self
+            .type_registry()
+            .read()
+            .get_with_type_path(type_path)
+            .map(|registration| registration.type_id())
+        else {
+            return false;
+        };
+        self.get(ptr)
+            .is_some_and(|entity| entity.contains_type_id(type_id))
+    }
+    /// Applies `f` to the component `T` of the data pointed to by `ptr` and returns its result.
+    /// Static data will be cloned into the active dynamic layer, unless it was already cloned before.
+    /// Returns [`None`] when the reference is [`Null`](DataRef::Null), the entity does not exist or does not have the component.
+    #[inline]
+    pub fn map_component<T: Component, R>(
+        &mut self,
+        ptr: DataRef,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> Option<R> {
+        self.get_component_mut::<T>(ptr)
+            .map(|mut component| f(&mut component))
+    }
+    /// Inserts the component `value` into the data pointed to by `ptr`, see [`insert_bundle`](DataWorlds::insert_bundle).
+    #[inline]
+    pub fn insert_component<T: Component>(&mut self, ptr: DataRef, value: T) -> DataRef {
+        self.insert_bundle(ptr, value)
+    }
+    /// Inserts all components of `bundle` into the data pointed to by `ptr`.
+    /// Static data will be cloned into the active dynamic layer first.
+    ///
+    /// Returns a reference to the dynamic data, or [`Null`](DataRef::Null) when the data does not exist.
+    pub fn insert_bundle<B: Bundle>(&mut self, ptr: DataRef, bundle: B) -> DataRef {
+        let Some((layer, entity)) = self.locate_mut(ptr) else {
+            return DataRef::Null;
+        };
+        match self
+            .dynamic_layers
+            .get_mut(&layer)
+            .and_then(|world| world.get_entity_mut(entity))
+        {
+            Some(mut entity_mut) => {
+                entity_mut.insert(bundle);
+                DataRef::Dynamic(layer, entity)
+            }
+            None => DataRef::Null,
+        }
+    }
+    /// Removes the component `T` from the data pointed to by `ptr`.
+    /// Static data will be cloned into the active dynamic layer first, so the static original keeps the component.
+    ///
+    /// Returns `true` if the component was present.
+    pub fn remove_component<T: Component>(&mut self, ptr: DataRef) -> bool {
+        if !self.get(ptr).is_some_and(|entity| entity.contains::<T>()) {
+            return false;
+        }
+        let Some((layer, entity)) = self.locate_mut(ptr) else {
+            return false;
+        };
+        self.dynamic_layers
+            .get_mut(&layer)
+            .and_then(|world| world.get_entity_mut(entity))
+            .and_then(|mut entity| entity.take::<T>())
+            .is_some()
+    }
+    /// Returns a guard that allows mutable access to the data pointed to by `ptr`,
+    /// which only transfers static data when a component is actually accessed mutably.
+    #[inline]
+    pub fn peek_mut(&mut self, ptr: DataRef) -> DataPeekMut<'_> {
+        DataPeekMut { data: self, ptr }
+    }
+    /// Returns a copy of the component `T` for each of `refs`, see [`get_component`](DataWorlds::get_component).
+    #[inline]
+    pub fn collect_component<T: Component + Clone>(&self, refs: &[DataRef]) -> Vec<Option<T>> {
+        self.get_components::<T>(refs)
+            .map(Option::<&T>::cloned)
+            .collect()
+    }
+    /// Iterates over references to the component `T` for each of `refs`, see [`get_component`](DataWorlds::get_component).
+    #[inline]
+    pub fn get_components<'a, T: Component>(
+        &'a self,
+        refs: &'a [DataRef],
+    ) -> impl Iterator<Item = Option<&'a T>> + 'a {
+        refs.iter().map(|&ptr| self.get_component::<T>(ptr))
+    }
+    /// Returns a mutable reference to the component `T` of the data pointed to by `ptr`.
+    /// Static data will be cloned into the active dynamic layer, unless it was already cloned before.
+    /// Returns [`None`] when the reference is [`Null`](DataRef::Null), the entity does not exist or does not have the component.
+    #[inline]
+    pub fn get_component_mut<T: Component>(&mut self, ptr: DataRef) -> Option<Mut<'_, T>> {
+        let (layer, entity) = self.locate_mut(ptr)?;
+        self.dynamic_layers.get_mut(&layer)?.get_mut::<T>(entity)
+    }
+    /// Calls `f` with a mutable reference to the component `T` of the data pointed to by `ptr` without triggering change detection.
+    /// Static data will be cloned into the active dynamic layer, unless it was already cloned before.
+    /// Returns [`None`] when the reference is [`Null`](DataRef::Null), the entity does not exist or does not have the component.
+    ///
+    /// Changes made through this reference will not be reported by [`changed_since`](DataWorlds::changed_since),
+    /// so they might be missing from incremental saves. This should only be used for internal fixups that do not change the meaning of the data.
+    #[inline]
+    pub fn get_component_mut_untracked<T: Component, R>(
+        &mut self,
+        ptr: DataRef,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> Option<R> {
+        let mut component = self.get_component_mut::<T>(ptr)?;
+        Some(f(component.bypass_change_detection()))
+    }
+    /// Returns the dynamic location of the data pointed to by `ptr`, transferring static data if nessesary.
+    #[inline]
+    fn locate_mut(&mut self, ptr: DataRef) -> Option<(LayerId, Entity)> {
+        match ptr {
+            DataRef::Static(entity) => self
+                .transfer(entity)
+                .inspect_err(|err| {
+                    if !matches!(err, TransferError::Missing(_)) {
+                        warn!("{err}");
+                    }
+                })
+                .ok(),
+            DataRef::Dynamic(layer, entity) => Some((layer, entity)),
+            DataRef::Null => None,
+        }
+    }
+    #[inline]
+    fn transfer(&mut self, entity: Entity) -> Result<(LayerId, Entity), TransferError> {
+        if let Some(&target) = self.transferred.get(&entity) {
+            if target.0 == self.active_layer() {
+                return Ok(target);
+            }
+        }
+        if self.frozen {
+            return Err(TransferError::Frozen(entity));
+        }
+        let target = self.copy_to_active(entity)?;
+        self.record_transfer(entity, target);
+        Ok(target)
+    }
+    /// Returns `true` if the static `entity` has no copy in the active layer yet.
+    #[inline]
+    fn needs_transfer(&self, entity: Entity) -> bool {
+        self.transferred
+            .get(&entity)
+            .is_none_or(|(layer, _)| *layer != self.active_layer())
+    }
+    /// Returns the components that are copied when transferring the static `entity` into the active layer,
+    /// together with the layer of the copy they are read from, or [`None`] for the static original.
+    fn transfer_source(&self, entity: Entity) -> Result<TransferSource, TransferError> {
+        match self.transferred.get(&entity) {
+            Some(&(layer, target)) => Ok((
+                Some((layer, target)),
+                reflect_components(
+                    &self.dynamic_layers[&layer],
+                    target,
+                    self.strict_transfer,
+                    &|_, _| true,
+                )?,
+            )),
+            None => Ok((
+                None,
+                reflect_components(
+                    &self.static_world,
+                    entity,
+                    self.strict_transfer,
+                    &|type_id, name| self.is_transferable(type_id, name),
+                )?,
+            )),
+        }
+    }
+    /// Returns an error if the static `entity` can not be transferred, without transferring it.
+    #[inline]
+    fn check_transfer(&self, entity: Entity) -> Result<(), TransferError> {
+        if self.frozen {
+            return Err(TransferError::Frozen(entity));
+        }
+        self.transfer_source(entity).map(drop)
+    }
+    /// Copies the static `entity` into the active layer without forwarding references to it.
+    /// When it was transferred into a lower layer before, that copy is used instead of the static original.
+    fn copy_to_active(&mut self, entity: Entity) -> Result<(LayerId, Entity), TransferError> {
+        let span = trace_span!("transfer_data", component_count = field::Empty).entered();
+        let (lower, components) = self.transfer_source(entity)?;
+        span.record("component_count", components.len());
+        let layer = self.active_layer();
+        let mut dynamic_world = self
+            .dynamic_layers
+            .remove(&layer)
+            .expect("Active layer should always exist");
+        let target = dynamic_world.spawn_empty().id();
+        match lower {
+            Some((lower_layer, source)) => copy_entity(
+                &components,
+                &self.dynamic_layers[&lower_layer],
+                &mut dynamic_world,
+                source,
+                target,
+            ),
+            None => {
+                copy_entity(
+                    &components,
+                    &self.static_world,
+                    &mut dynamic_world,
+                    entity,
+                    target,
+                );
+                if let Some(TransferHook(hook)) = &self.on_transfer {
+                    hook(&mut dynamic_world, DataRef::Dynamic(layer, target));
+                }
+            }
+        }
+        self.dynamic_layers.insert(layer, dynamic_world);
+        span.exit();
+        Ok((layer, target))
+    }
+    /// Forwards references to the static `entity` to its dynamic copy at `target`.
+    #[inline]
+    fn record_transfer(&mut self, entity: Entity, (layer, target): (LayerId, Entity)) {
+        self.insert_transfer(entity, (layer, target));
+        self.transfer_events.push(DataTransferred {
+            old: DataRef::Static(entity),
+            new: DataRef::Dynamic(layer, target),
+        });
+    }
+    /// Returns `true` if components of this type are copied when static data is transferred.
+    #[inline]
+    fn is_transferable(&self, type_id: TypeId, name: &str) -> bool {
+        !self.transfer_skips.contains(&type_id)
+            && self
+                .transfer_filter
+                .as_ref()
+                .is_none_or(|TransferFilter(filter)| filter(type_id, name))
+    }
+    /// Runs `system` on the dynamic layer after static data was transferred into it, with the reference to the dynamic copy as input.
+    /// This can be used to patch transferred data, e.g. to recompute cached fields. Replaces any previous hook.
+    ///
+    /// The system is created anew for each transfer, so local state is not kept between runs.
+    pub fn set_on_transfer<M>(
+        &mut self,
+        system: impl IntoSystem<DataRef, (), M> + Clone + Send + Sync + 'static,
+    ) {
+        self.on_transfer = Some(TransferHook(Box::new(move |world, ptr| {
+            world.run_system_once_with(ptr, system.clone());
+        })));
+    }
+    /// Enables or disables frozen mode.
+    /// While frozen, static data is never transferred, so mutable access to static data that was not transferred before will fail and log a warning.
+    /// Mutable access to dynamic data is still allowed.
+    #[inline]
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
+    /// Returns `true` if frozen mode is enabled, see [`set_frozen`](DataWorlds::set_frozen).
+    #[inline]
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+    /// Excludes the component `T` when transferring static data into a dynamic layer, so dynamic copies never contain it.
+    /// This can be used for static-only metadata.
+    #[inline]
+    pub fn skip_on_transfer<T: Component>(&mut self) {
+        self.transfer_skips.insert(TypeId::of::<T>());
+    }
+    /// Sets a filter that decides for each component whether it is copied when transferring static data into a dynamic layer.
+    /// `filter` receives the type id and name of the component, returning `false` skips the component. Replaces any previous filter.
+    ///
+    /// Components excluded using [`skip_on_transfer`](DataWorlds::skip_on_transfer) are skipped regardless of the filter.
+    #[inline]
+    pub fn set_transfer_filter(
+        &mut self,
+        filter: impl Fn(TypeId, &str) -> bool + Send + Sync + 'static,
+    ) {
+        self.transfer_filter = Some(TransferFilter(Box::new(filter)));
+    }
+    /// Enables or disables strict transfers.
+    /// By default components that are not rust types are skipped with a warning when copying data between worlds,
+    /// in strict mode the copy fails with [`TransferError::NotRustType`] instead.
+    #[inline]
+    pub fn set_strict_transfer(&mut self, strict: bool) {
+        self.strict_transfer = strict;
+    }
+    /// Returns all [transfer events](DataTransferred) that happened since the last call.
+    ///
+    /// This should be called regularly by a system that forwards the events into the app world.
+    #[inline]
+    pub fn drain_transfer_events(&mut self) -> Vec<DataTransferred> {
+        std::mem::take(&mut self.transfer_events)
+    }
+    /// Moves all dynamic data from every layer into the static world, making the current state the new immutable baseline.
+    ///
+    /// Data that was transferred from the static world will replace its static original, so static references stay valid.
+    /// When it was transferred into multiple layers, only the copy in the highest layer is kept.
+    /// When the static original no longer exists, the data is added as new static data instead.
+    /// Components of the static original that are not transferred, see [`skip_on_transfer`](DataWorlds::skip_on_transfer), are kept.
+    /// All other data is added as new static data and dynamic references between the committed data are rewritten to their new static locations.
+    /// Dynamic references stored outside of the data worlds will be invalidated.
+    ///
+    /// When any dynamic component can not be copied an error is returned and no data is changed.
+    pub fn commit_dynamic_to_static(&mut self) -> Result<(), TransferError> {
+        let span = trace_span!("commit_dynamic_data_world").entered();
+        let shadowed = self
+            .shadowed
+            .values()
+            .flat_map(|shadowed| shadowed.iter().map(|(&source, &target)| (target, source)))
+            .collect::<HashMap<_, _>>();
+        let mut sources = Vec::new();
+        for layer in self.layers() {
+            let dynamic_world = &self.dynamic_layers[&layer];
+            for entity_ref in dynamic_world.iter_entities() {
+                let entity = entity_ref.id();
+                if shadowed.contains_key(&(layer, entity)) {
+                    continue;
+                }
+                sources.push((
+                    layer,
+                    entity,
+                    reflect_components(dynamic_world, entity, self.strict_transfer, &|_, _| true)?,
+                ));
+            }
+        }
+        let mut transferable = EntityHashMap::default();
+        for &source in self.transferred.keys() {
+            if self.static_world.get_entity(source).is_some() {
+                transferable.insert(
+                    source,
+                    reflect_components(
+                        &self.static_world,
+                        source,
+                        self.strict_transfer,
+                        &|type_id, name| self.is_transferable(type_id, name),
+                    )?,
+                );
+            }
+        }
+        let mut entity_map = self
+            .transferred
+            .drain()
+            .map(|(source, target)| (target, source))
+            .chain(shadowed)
+            .collect::<HashMap<_, _>>();
+        self.shadowed.clear();
+        for (layer, entity, components) in sources {
+            let original = entity_map
+                .get(&(layer, entity))
+                .and_then(|&target| self.static_world.get_entity_mut(target));
+            let target = match original {
+                Some(mut entity_mut) => {
+                    let target = entity_mut.id();
+                    for reflect_component in transferable.get(&target).into_iter().flatten() {
+                        reflect_component.remove(&mut entity_mut);
+                    }
+                    target
+                }
+                None => {
+                    let target = self.static_world.spawn_empty().id();
+                    entity_map.insert((layer, entity), target);
+                    target
+                }
+            };
+            copy_entity(
+                &components,
+                &self.dynamic_layers[&layer],
+                &mut self.static_world,
+                entity,
+                target,
+            );
+        }
+        for &target in entity_map.values() {
+            map_entity_refs(&mut self.static_world, target, &mut |ptr| match ptr {
+                DataRef::Dynamic(layer, entity) => entity_map
+                    .get(&(layer, entity))
+                    .map_or(ptr, |&target| DataRef::Static(target)),
+                _ => ptr,
+            });
+        }
+        for dynamic_world in self.dynamic_layers.values_mut() {
+            dynamic_world.clear_entities();
+        }
+        span.exit();
+        Ok(())
+    }
+    /// Returns the current change tick of the active layer and advances it,
+    /// so every change made after this call will be reported by [`changed_since`](DataWorlds::changed_since).
+    #[inline]
+    pub fn last_change_tick(&self) -> Tick {
+        self.dynamic_world().increment_change_tick()
+    }
+    /// Returns all entities in the active layer that had any component added or changed after `tick`.
+    pub fn changed_since(&self, tick: Tick) -> Vec<Entity> {
+        let dynamic_world = self.dynamic_world();
+        let this_run = dynamic_world.read_change_tick();
+        dynamic_world
+            .iter_entities()
+            .filter(|entity_ref| {
+                entity_ref.archetype().components().any(|component_id| {
+                    entity_ref
+                        .get_change_ticks_by_id(component_id)
+                        .is_some_and(|ticks| ticks.is_changed(tick, this_run))
+                })
+            })
+            .map(|entity_ref| entity_ref.id())
+            .collect()
+    }
+    /// Despawns the dynamic data pointed to by `ptr` and returns whether it existed.
+    /// Static data pointing to the despawned data will be read from a lower layer or the static world again.
+    ///
+    /// Returns [`DataError::Referenced`] without despawning when other data still references it, see [`ref_count`](DataWorlds::ref_count).
+    /// Use [`despawn_forced`](DataWorlds::despawn_forced) to skip this check.
+    ///
+    /// Static data is immutable and can not be despawned, this will return `false` and log a warning instead.
+    pub fn despawn(&mut self, ptr: DataRef) -> Result<bool, DataError> {
+        if let DataRef::Dynamic(..) = ptr {
+            let count = self.ref_count(ptr);
+            if count > 0 {
+                return Err(DataError::Referenced { ptr, count });
+            }
+        }
+        Ok(self.despawn_forced(ptr))
+    }
+    /// Despawns the dynamic data pointed to by `ptr` even if it is still referenced by other data, see [`despawn`](DataWorlds::despawn).
+    pub fn despawn_forced(&mut self, ptr: DataRef) -> bool {
+        match ptr {
+            DataRef::Static(_) => {
+                warn!("tried to despawn static data {ptr:?}");
+                false
+            }
+            DataRef::Dynamic(layer, entity) => {
+                let Some(dynamic_world) = self.dynamic_layers.get_mut(&layer) else {
+                    return false;
+                };
+                if !dynamic_world.despawn(entity) {
+                    return false;
+                }
+                self.unlink_transfer((layer, entity));
+                true
+            }
+            DataRef::Null => false,
+        }
+    }
+    /// Transfers the data pointed to by `ptr` and all static data reachable through its [DataRef] fields into the active dynamic layer.
+    /// All static references between the transferred data are rewritten to point to the dynamic copies,
+    /// so the whole subtree stays consistent when being mutated.
+    ///
+    /// Returns the dynamic location of the data, or [`Null`](DataRef::Null) when it does not exist.
+    pub fn transfer_recursive(&mut self, ptr: DataRef) -> DataRef {
+        let Some(root) = self.locate_mut(ptr).filter(|(layer, entity)| {
+            self.dynamic_layers
+                .get(layer)
+                .is_some_and(|world| world.get_entity(*entity).is_some())
+        }) else {
+            return DataRef::Null;
+        };
+        let mut visited = HashSet::new();
+        let mut stack = vec![root];
+        while let Some((layer, entity)) = stack.pop() {
+            if !visited.insert((layer, entity)) {
+                continue;
+            }
+            let Some(entity_ref) = self
+                .dynamic_layers
+                .get(&layer)
+                .and_then(|world| world.get_entity(entity))
+            else {
+                continue;
+            };
+            let mut references = Vec::new();
+            visit_entity_refs(&self.dynamic_layers[&layer], entity_ref, &mut |ptr| {
+                if let DataRef::Static(entity) = ptr {
+                    references.push(entity);
+                }
+            });
+            stack.extend(
+                references
+                    .into_iter()
+                    .filter_map(|entity| self.locate_mut(DataRef::Static(entity))),
+            );
+            let transferred = &self.transferred;
+            let dynamic_world = self
+                .dynamic_layers
+                .get_mut(&layer)
+                .expect("Layer should exist");
+            map_entity_refs(dynamic_world, entity, &mut |ptr| match ptr {
+                DataRef::Static(entity) => transferred
+                    .get(&entity)
+                    .map_or(ptr, |&(layer, target)| DataRef::Dynamic(layer, target)),
+                _ => ptr,
+            });
+        }
+        DataRef::Dynamic(root.0, root.1)
+    }
+    /// Transfers all static data into the active dynamic layer, so everything becomes mutable.
+    /// All static references in the transferred data are rewritten to point to the dynamic copies.
+    /// Data that was transferred before is not copied again, but its references are rewritten as well.
+    ///
+    /// Returns a map from each static entity to its dynamic copy.
+    pub fn transfer_all_static(&mut self) -> HashMap<Entity, Entity> {
+        let span = trace_span!("transfer_all_static", entity_count = field::Empty).entered();
+        let entities = self
+            .static_world
+            .iter_entities()
+            .map(|entity_ref| entity_ref.id())
+            .collect::<Vec<_>>();
+        let targets = entities
+            .into_iter()
+            .filter_map(|entity| {
+                self.locate_mut(DataRef::Static(entity))
+                    .map(|target| (entity, target))
+            })
+            .collect::<Vec<_>>();
+        span.record("entity_count", targets.len());
+        for &(_, (layer, target)) in &targets {
+            let transferred = &self.transferred;
+            let dynamic_world = self
+                .dynamic_layers
+                .get_mut(&layer)
+                .expect("Layer should exist");
+            map_entity_refs(dynamic_world, target, &mut |ptr| match ptr {
+                DataRef::Static(entity) => transferred
+                    .get(&entity)
+                    .map_or(ptr, |&(layer, target)| DataRef::Dynamic(layer, target)),
+                _ => ptr,
+            });
+        }
+        span.exit();
+        targets
+            .into_iter()
+            .map(|(entity, (_, target))| (entity, target))
+            .collect()
+    }
+    /// Returns all data reachable from `roots` by following [DataRef] fields, ordered so that referenced data comes before the data referencing it.
+    /// References are resolved, so transferred data is only returned once. Data that does not exist is skipped.
+    ///
+    /// Returns a [CycleError] naming the involved data when the references form a cycle.
+    pub fn iter_topological(&self, roots: &[DataRef]) -> Result<Vec<DataRef>, CycleError> {
+        let mut order = Vec::new();
+        let mut done = HashSet::new();
+        let mut path = Vec::new();
+        for &root in roots {
+            self.visit_topological(self.resolve(root), &mut path, &mut done, &mut order)?;
+        }
+        Ok(order)
+    }
+    fn visit_topological(
+        &self,
+        ptr: DataRef,
+        path: &mut Vec<DataRef>,
+        done: &mut HashSet<DataRef>,
+        order: &mut Vec<DataRef>,
+    ) -> Result<(), CycleError> {
+        if done.contains(&ptr) || self.get(ptr).is_none() {
+            return Ok(());
+        }
+        if let Some(start) = path.iter().position(|&visiting| visiting == ptr) {
+            return Err(CycleError {
+                entities: path[start..].to_vec(),
+            });
+        }
+        let mut references = Vec::new();
+        self.visit_refs(ptr, |target| {
+            if !target.is_null() {
+                references.push(self.resolve(target));
+            }
+        });
+        path.push(ptr);
+        for target in references {
+            self.visit_topological(target, path, done, order)?;
+        }
+        path.pop();
+        done.insert(ptr);
+        order.push(ptr);
+        Ok(())
+    }
+    /// Despawns all dynamic data in every layer that can not be reached from any of `roots` by following [DataRef] fields.
+    /// References are followed through static data as well, transferred data that is despawned will be read from a lower layer or the static world again.
+    ///
+    /// Returns the number of despawned entities.
+    pub fn garbage_collect(&mut self, roots: &[DataRef]) -> usize {
+        let span = trace_span!("garbage_collect").entered();
+        let mut reachable = HashSet::new();
+        let mut stack = roots
+            .iter()
+            .map(|&ptr| self.resolve(ptr))
+            .collect::<Vec<_>>();
+        while let Some(ptr) = stack.pop() {
+            if ptr.is_null() || !reachable.insert(ptr) {
+                continue;
+            }
+            let (world, entity) = match ptr {
+                DataRef::Static(entity) => (&self.static_world, entity),
+                DataRef::Dynamic(layer, entity) => match self.dynamic_layers.get(&layer) {
+                    Some(world) => (world, entity),
+                    None => continue,
+                },
+                DataRef::Null => unreachable!(),
+            };
+            let Some(entity_ref) = world.get_entity(entity) else {
+                continue;
+            };
+            visit_entity_refs(world, entity_ref, &mut |ptr| stack.push(self.resolve(ptr)));
+        }
+        let garbage = self
+            .layers()
+            .flat_map(|layer| {
+                self.dynamic_layers[&layer]
+                    .iter_entities()
+                    .map(move |entity_ref| DataRef::Dynamic(layer, entity_ref.id()))
+            })
+            .filter(|ptr| !reachable.contains(ptr))
+            .collect::<Vec<_>>();
+        let count = garbage
+            .into_iter()
+            .filter(|&ptr| self.despawn_forced(ptr))
+            .count();
+        debug!("despawned {count} unreachable entities");
+        span.exit();
+        count
+    }
+    /// Checks every [DataRef] stored in the static world and all dynamic layers
+    /// and returns `(holder, target)` pairs for all non-null references whose target does not exist.
+    ///
+    /// This can be used to detect corrupted data after loading.
+    pub fn validate_references(&self) -> Vec<(DataRef, DataRef)> {
+        let span = trace_span!("validate_references").entered();
+        let worlds = [(None, &self.static_world)].into_iter().chain(
+            self.layers()
+                .map(|layer| (Some(layer), &self.dynamic_layers[&layer])),
+        );
+        let mut dangling = Vec::new();
+        for (layer, world) in worlds {
+            for entity_ref in world.iter_entities() {
+                let holder = match layer {
+                    Some(layer) => DataRef::Dynamic(layer, entity_ref.id()),
+                    None => DataRef::Static(entity_ref.id()),
+                };
+                visit_entity_refs(world, entity_ref, &mut |ptr| {
+                    if !ptr.is_null() && self.get(ptr).is_none() {
+                        dangling.push((holder, ptr));
+                    }
+                });
+            }
+        }
+        span.exit();
+        dangling
+    }
+    /// Returns the number of [DataRef] fields in the static world and all dynamic layers that point to the data pointed to by `ptr`.
+    /// References are resolved before comparing, references of data to itself and of transferred static data are not counted.
+    ///
+    /// This scans all data, so it should not be used in hot paths.
+    pub fn ref_count(&self, ptr: DataRef) -> usize {
+        let target = self.resolve(ptr);
+        if target.is_null() {
+            return 0;
+        }
+        let static_data = self
+            .iter_static()
+            .filter(|(_, entity_ref)| !self.transferred.contains_key(&entity_ref.id()))
+            .map(|(holder, entity_ref)| (holder, &self.static_world, entity_ref));
+        let dynamic_data = self.layers().flat_map(|layer| {
+            let world = &self.dynamic_layers[&layer];
+            world.iter_entities().map(move |entity_ref| {
+                (DataRef::Dynamic(layer, entity_ref.id()), world, entity_ref)
+            })
+        });
+        let mut count = 0;
+        for (holder, world, entity_ref) in static_data.chain(dynamic_data) {
+            if holder == target {
+                continue;
+            }
+            visit_entity_refs(world, entity_ref, &mut |ptr| {
+                if self.resolve(ptr) == target {
+                    count += 1;
+                }
+            });
+        }
+        count
+    }
+    /// Iterates over all static data.
+    #[inline]
+    pub fn iter_static(&self) -> impl Iterator<Item = (DataRef, EntityRef<'_>)> + '_ {
+        self.static_world
+            .iter_entities()
+            .map(|entity_ref| (DataRef::Static(entity_ref.id()), entity_ref))
+    }
+    /// Iterates over all dynamic data in the active layer.
+    #[inline]
+    pub fn iter_dynamic(&self) -> impl Iterator<Item = (DataRef, EntityRef<'_>)> + '_ {
+        let layer = self.active_layer();
+        self.dynamic_world()
+            .iter_entities()
+            .map(move |entity_ref| (DataRef::Dynamic(layer, entity_ref.id()), entity_ref))
+    }
+    /// Returns the first data with a component `T` matching `predicate`, searching the static world first and then all dynamic layers.
+    /// Static data that was transferred to a dynamic layer is only matched by its dynamic copy.
+    #[inline]
+    pub fn find<T: Component>(&self, predicate: impl Fn(&T) -> bool) -> Option<DataRef> {
+        self.find_iter(predicate).next()
+    }
+    /// Returns all data with a component `T` matching `predicate`, see [`find`](DataWorlds::find).
+    #[inline]
+    pub fn find_all<T: Component>(&self, predicate: impl Fn(&T) -> bool) -> Vec<DataRef> {
+        self.find_iter(predicate).collect()
+    }
+    fn find_iter<'a, T: Component>(
+        &'a self,
+        predicate: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = DataRef> + 'a {
+        let static_data = self
+            .iter_static()
+            .filter(|(_, entity_ref)| !self.transferred.contains_key(&entity_ref.id()));
+        let dynamic_data = self.layers().flat_map(|layer| {
+            self.dynamic_layers[&layer]
+                .iter_entities()
+                .map(move |entity_ref| (DataRef::Dynamic(layer, entity_ref.id()), entity_ref))
+        });
+        static_data
+            .chain(dynamic_data)
+            .filter(move |(_, entity_ref)| entity_ref.get::<T>().is_some_and(&predicate))
+            .map(|(ptr, _)| ptr)
+    }
+    /// Builds an index mapping each value of the component `T` to the data it is attached to, see [`lookup`](DataWorlds::lookup).
+    /// Both worlds are scanned like in [`find`](DataWorlds::find), if multiple entities share a key the last one found is used.
+    ///
+    /// The index is a snapshot and is not updated automatically.
+    /// It has to be rebuilt after spawning, despawning, transferring or reloading data, or after changing the indexed component.
+    pub fn build_index<T: Component + Hash + Eq + Clone>(&mut self) {
+        let index = self
+            .find_iter::<T>(|_| true)
+            .filter_map(|ptr| Some((self.get_component::<T>(ptr)?.clone(), ptr)))
+            .collect::<HashMap<T, DataRef>>();
+        self.indices.insert(TypeId::of::<T>(), Box::new(index));
+    }
+    /// Returns the data whose component `T` equals `key`, using the index created by [`build_index`](DataWorlds::build_index).
+    /// Returns [`None`] when no index was built for `T` or the key is not indexed.
+    #[inline]
+    pub fn lookup<T: Component + Hash + Eq + Clone>(&self, key: &T) -> Option<DataRef> {
+        self.indices
+            .get(&TypeId::of::<T>())?
+            .downcast_ref::<HashMap<T, DataRef>>()?
+            .get(key)
+            .copied()
+    }
+    /// Removes the index for the component `T` created by [`build_index`](DataWorlds::build_index).
+    #[inline]
+    pub fn invalidate_index<T: Component>(&mut self) {
+        self.indices.remove(&TypeId::of::<T>());
+    }
+    /// Spawns a copy of the data pointed to by `ptr` and returns a reference to it.
+    /// Dynamic data is copied into the same layer, static data is copied into the active layer.
+    /// Unlike [transferred](DataWorlds::get_mut) data the copy is independent from its source.
+    ///
+    /// [DataRef] fields are copied as-is, so the copy references the same data as the original.
+    /// Returns [`Null`](DataRef::Null) when the data does not exist or could not be copied.
+    pub fn clone_dynamic(&mut self, ptr: DataRef) -> DataRef {
+        let (source_world, entity, layer) = match self.resolve(ptr) {
+            DataRef::Static(entity) => (&self.static_world, entity, self.active_layer()),
+            DataRef::Dynamic(layer, entity) => match self.dynamic_layers.get(&layer) {
+                Some(world) => (world, entity, layer),
+                None => return DataRef::Null,
+            },
+            DataRef::Null => return DataRef::Null,
+        };
+        let values = match reflect_values(source_world, entity, self.strict_transfer) {
+            Ok(values) => values,
+            Err(TransferError::Missing(_)) => return DataRef::Null,
+            Err(err) => {
+                warn!("{err}");
+                return DataRef::Null;
+            }
+        };
+        let registry = source_world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let mut target = self
+            .dynamic_layers
+            .get_mut(&layer)
+            .expect("Layer should exist")
+            .spawn_empty();
+        for (reflect_component, value) in values {
+            reflect_component.insert(&mut target, &*value, &registry);
+        }
+        DataRef::Dynamic(layer, target.id())
+    }
+    /// Copies all components of the data pointed to by `ptr`, so they can be spawned again using [`spawn_reflected`](DataWorlds::spawn_reflected).
+    /// Returns [`None`] when the data does not exist or could not be copied.
+    pub fn extract_components(&self, ptr: DataRef) -> Option<ReflectedEntity> {
+        let (world, entity) = match self.resolve(ptr) {
+            DataRef::Static(entity) => (&self.static_world, entity),
+            DataRef::Dynamic(layer, entity) => (self.dynamic_layers.get(&layer)?, entity),
+            DataRef::Null => return None,
+        };
+        match reflect_values(world, entity, self.strict_transfer) {
+            Ok(components) => Some(ReflectedEntity { components }),
+            Err(TransferError::Missing(_)) => None,
+            Err(err) => {
+                warn!("{err}");
+                None
+            }
+        }
+    }
+    /// Spawns data with the components of `entity` in the active layer and returns a reference to it.
+    pub fn spawn_reflected(&mut self, entity: ReflectedEntity) -> DataRef {
+        let layer = self.active_layer();
+        let registry = self.type_registry().clone();
+        let registry = registry.read();
+        let mut target = self.dynamic_world_mut().spawn_empty();
+        for (reflect_component, value) in entity.components {
+            reflect_component.insert(&mut target, &*value, &registry);
+        }
+        DataRef::Dynamic(layer, target.id())
+    }
+    /// Spawns a copy of the data pointed to by `ptr` and of all data reachable through its [DataRef] fields, see [`clone_dynamic`](DataWorlds::clone_dynamic).
+    /// References between the copied data are rewritten to point to the copies, so the copy is independent from its source.
+    ///
+    /// Returns [`Null`](DataRef::Null) when the data does not exist or could not be copied.
+    pub fn clone_dynamic_deep(&mut self, ptr: DataRef) -> DataRef {
+        let root = self.resolve(ptr);
+        let mut copies = HashMap::new();
+        let mut stack = vec![root];
+        while let Some(ptr) = stack.pop() {
+            if ptr.is_null() || copies.contains_key(&ptr) {
+                continue;
+            }
+            let copy = self.clone_dynamic(ptr);
+            if copy.is_null() {
+                continue;
+            }
+            copies.insert(ptr, copy);
+            self.visit_refs(ptr, |target| stack.push(self.resolve(target)));
+        }
+        let transferred = &self.transferred;
+        for &copy in copies.values() {
+            let DataRef::Dynamic(layer, entity) = copy else {
+                unreachable!("Copies should always be dynamic");
+            };
+            let dynamic_world = self
+                .dynamic_layers
+                .get_mut(&layer)
+                .expect("Layer should exist");
+            map_entity_refs(dynamic_world, entity, &mut |ptr| {
+                let resolved = match ptr {
+                    DataRef::Static(entity) => transferred
+                        .get(&entity)
+                        .map_or(ptr, |&(layer, target)| DataRef::Dynamic(layer, target)),
+                    _ => ptr,
+                };
+                copies.get(&resolved).copied().unwrap_or(ptr)
+            });
+        }
+        copies.get(&root).copied().unwrap_or(DataRef::Null)
+    }
+    /// Returns the world that stores the data pointed to by `ptr`, or [`None`] for [`Null`](DataRef::Null) references and missing layers.
+    /// References are not forwarded, use [`resolve`](DataWorlds::resolve) first to get the world of transferred data.
+    ///
+    /// This can be used for low-level access, e.g. with a custom [QueryState].
+    #[inline]
+    pub fn world_for(&self, ptr: DataRef) -> Option<&World> {
+        match ptr {
+            DataRef::Static(_) => Some(&self.static_world),
+            DataRef::Dynamic(layer, _) => self.dynamic_layers.get(&layer),
+            DataRef::Null => None,
+        }
+    }
+    /// Returns the number of entities in the static world.
+    #[inline]
+    pub fn static_entity_count(&self) -> u32 {
+        self.static_world.entities().len()
+    }
+    /// Returns the number of entities in the active layer.
+    #[inline]
+    pub fn dynamic_entity_count(&self) -> u32 {
+        self.dynamic_world().entities().len()
+    }
+    /// Estimates the memory used by the data worlds.
+    /// Component sizes are taken from their memory layout, so heap allocations owned by components are not included.
+    pub fn estimated_size(&self) -> DataSizeReport {
+        let mut report = DataSizeReport {
+            static_entities: self.static_world.entities().len(),
+            static_bytes: estimate_component_bytes(&self.static_world),
+            ..Default::default()
+        };
+        for layer in self.layers() {
+            let dynamic_world = &self.dynamic_layers[&layer];
+            report.dynamic_entities += dynamic_world.entities().len();
+            report.dynamic_bytes += estimate_component_bytes(dynamic_world);
+        }
+        report
+    }
+    /// Returns the number of static entities that were transferred to any dynamic layer.
+    #[inline]
+    pub fn transferred_count(&self) -> usize {
+        self.transferred.len()
+    }
+    /// Iterates over all static data that was transferred to a dynamic layer, yielding the static original and its dynamic copy.
+    #[inline]
+    pub fn shadowed_entities(&self) -> impl Iterator<Item = (DataRef, DataRef)> + '_ {
+        self.transferred.iter().map(|(&source, &(layer, target))| {
+            (DataRef::Static(source), DataRef::Dynamic(layer, target))
+        })
+    }
+    /// Forgets all transferred static data whose dynamic copy no longer exists, so reads fall back to a copy in a lower layer or the static original.
+    /// This is only needed when dynamic data was despawned without using [`despawn`](DataWorlds::despawn), e.g. by a system.
+    ///
+    /// Returns the number of removed entries.
+    pub fn prune_transfer_map(&mut self) -> usize {
+        let mut count = 0;
+        loop {
+            let stale = self
+                .transferred
+                .iter()
+                .filter(|(_, (layer, target))| {
+                    self.dynamic_layers
+                        .get(layer)
+                        .is_none_or(|world| world.get_entity(*target).is_none())
+                })
+                .map(|(&source, _)| source)
+                .collect::<Vec<_>>();
+            if stale.is_empty() {
+                return count;
+            }
+            count += stale.len();
+            for source in stale {
+                self.remove_transfer(source);
+            }
+        }
+    }
+    /// Returns `true` if `ptr` points to static data that was transferred to a dynamic layer.
+    #[inline]
+    pub fn is_shadowed(&self, ptr: DataRef) -> bool {
+        match ptr {
+            DataRef::Static(entity) => self.transferred.contains_key(&entity),
+            _ => false,
+        }
+    }
+    /// Discards all changes made to transferred data by despawning the dynamic copy, so future reads fall back to a copy in a lower layer or the static original.
+    /// `ptr` can either point to the dynamic copy or the static original.
+    ///
+    /// Returns `false` if the data was not transferred from the static world.
+    pub fn reset_entity_to_static(&mut self, ptr: DataRef) -> bool {
+        let DataRef::Dynamic(layer, entity) = self.resolve(ptr) else {
+            return false;
+        };
+        let Some(source) = self
+            .transferred
+            .iter()
+            .find_map(|(&source, &target)| (target == (layer, entity)).then_some(source))
+        else {
+            return false;
+        };
+        self.remove_transfer(source);
+        if let Some(dynamic_world) = self.dynamic_layers.get_mut(&layer) {
+            dynamic_world.despawn(entity);
+        }
+        true
+    }
+    /// Lists all components of transferred data that differ from their static original.
+    /// Components that can not be reflected are only compared by their presence,
+    /// components that are not transferred, see [`skip_on_transfer`](DataWorlds::skip_on_transfer), are ignored.
+    pub fn diff_against_static(&self) -> Vec<RefDiff> {
+        let mut transferred = self.transferred.iter().collect::<Vec<_>>();
+        transferred.sort_unstable_by_key(|(source, _)| **source);
+        transferred
+            .into_iter()
+            .filter_map(|(&source, &(layer, target))| {
+                let source_ref = self.static_world.get_entity(source)?;
+                let target_world = self.dynamic_layers.get(&layer)?;
+                let target_ref = target_world.get_entity(target)?;
+                let changed = diff_components(
+                    &self.static_world,
+                    source_ref,
+                    target_world,
+                    target_ref,
+                    &|type_id, name| self.is_transferable(type_id, name),
+                );
+                (!changed.is_empty()).then_some(RefDiff {
+                    source,
+                    target: DataRef::Dynamic(layer, target),
+                    changed,
+                })
+            })
+            .collect()
+    }
+    /// Captures the current state of all dynamic layers, which can later be restored using [`restore`](DataWorlds::restore).
+    /// This can be used to implement undo.
+    pub fn snapshot(&self) -> DataSnapshot {
+        let span = trace_span!("snapshot_dynamic_data_worlds").entered();
+        let snapshot = DataSnapshot {
+            layers: self
+                .layers()
+                .map(|layer| {
+                    (
+                        layer,
+                        scene_from_world(&self.dynamic_layers[&layer], &self.resource_filter),
+                    )
+                })
+                .collect(),
+            next_layer: self.next_layer,
+            transferred: self.transferred.clone(),
+            shadowed: self.shadowed.clone(),
+        };
+        span.exit();
+        snapshot
+    }
+    /// Replaces all dynamic layers with the state captured in `snapshot`.
+    /// Entities keep their ids, so references taken before the snapshot stay valid.
+    ///
+    /// [Preserved resources](DataWorlds::preserve_resource) not contained in the snapshot are kept for layers that still exist.
+    pub fn restore(&mut self, snapshot: DataSnapshot) -> Result<(), SceneSpawnError> {
+        let span = trace_span!("restore_dynamic_data_worlds").entered();
+        let mut dynamic_layers = HashMap::with_capacity(snapshot.layers.len());
+        for (layer, scene) in &snapshot.layers {
+            let mut dynamic_world = self.empty_dynamic_world();
+            write_scene_preserving_ids(&mut dynamic_world, scene)?;
+            dynamic_layers.insert(*layer, dynamic_world);
+        }
+        for (layer, dynamic_world) in &mut dynamic_layers {
+            let Some(previous) = self.dynamic_layers.get_mut(layer) else {
+                continue;
+            };
+            for move_resource in self.preserved_resources.values() {
+                move_resource(previous, dynamic_world);
+            }
+        }
+        self.layer_stack = snapshot
+            .layers
+            .iter()
+            .map(|(layer, _)| *layer)
+            .filter(|&layer| layer != LayerId::BASE)
+            .collect();
+        self.dynamic_layers = dynamic_layers;
+        self.next_layer = snapshot.next_layer;
+        self.transferred = snapshot.transferred;
+        self.shadowed = snapshot.shadowed;
+        span.exit();
+        Ok(())
+    }
+    /// Rewrites static references stored in dynamic data that point to already transferred data, so they point to the dynamic copy directly.
+    /// This keeps the references between dynamic data consistent after transferring data one by one.
+    ///
+    /// Returns the number of rewritten references.
+    pub fn repair_references(&mut self) -> usize {
+        let span = trace_span!("repair_references").entered();
+        let transferred = &self.transferred;
+        let mut count = 0;
+        for dynamic_world in self.dynamic_layers.values_mut() {
+            let stale = dynamic_world
+                .iter_entities()
+                .filter(|&entity_ref| {
+                    let mut is_stale = false;
+                    visit_entity_refs(dynamic_world, entity_ref, &mut |ptr| {
+                        is_stale |= matches!(ptr, DataRef::Static(entity) if transferred.contains_key(&entity));
+                    });
+                    is_stale
+                })
+                .map(|entity_ref| entity_ref.id())
+                .collect::<Vec<_>>();
+            for entity in stale {
+                map_entity_refs(dynamic_world, entity, &mut |ptr| match ptr {
+                    DataRef::Static(entity) => match transferred.get(&entity) {
+                        Some(&(layer, target)) => {
+                            count += 1;
+                            DataRef::Dynamic(layer, target)
+                        }
+                        None => ptr,
+                    },
+                    _ => ptr,
+                });
+            }
+        }
+        debug!("repaired {count} references");
+        span.exit();
+        count
+    }
+    /// Returns the type registry used by the static world.
+    #[inline]
+    pub fn type_registry(&self) -> &AppTypeRegistry {
+        self.static_world.resource::<AppTypeRegistry>()
+    }
+    /// Registers `T` in the type registries of all worlds, so it can be stored, transferred and serialized.
+    /// This allows adding data types after construction.
+    pub fn register<T: GetTypeRegistration>(&mut self) {
+        let worlds = [&self.static_world]
+            .into_iter()
+            .chain(self.dynamic_layers.values());
+        for world in worlds {
+            world.resource::<AppTypeRegistry>().write().register::<T>();
+        }
+    }
+}
+
+/// Magic bytes at the start of binary data.
+const BINARY_MAGIC: [u8; 4] = *b"DWLD";
+/// Version of the binary format, has to be increased whenever the format changes.
+const BINARY_VERSION: u16 = 1;
+/// Magic bytes at the start of compressed files.
+const COMPRESSED_MAGIC: [u8; 4] = *b"DWLZ";
+/// Version of the compressed format, has to be increased whenever the format changes.
+const COMPRESSED_VERSION: u16 = 1;
+
+/// Compares all components and resources of `scene`, parsed as an untyped RON value, against the types registered in `type_registry`.
+fn check_scene_schema(type_registry: &AppTypeRegistry, scene: ron::Value) -> Vec<SchemaMismatch> {
+    let registry = type_registry.read();
+    let ron::Value::Map(scene) = scene else {
+        return Vec::new();
+    };
+    let get = |map: &ron::Map, key: &str| {
+        map.iter()
+            .find(|(name, _)| matches!(name, ron::Value::String(name) if name == key))
+            .map(|(_, value)| value.clone())
+    };
+    let resources = get(&scene, "resources").into_iter();
+    let components = get(&scene, "entities")
+        .into_iter()
+        .flat_map(|entities| match entities {
+            ron::Value::Map(entities) => entities.values().cloned().collect(),
+            _ => Vec::new(),
+        })
+        .filter_map(|entity| match entity {
+            ron::Value::Map(entity) => get(&entity, "components"),
+            _ => None,
+        });
+    let mut mismatches = Vec::new();
+    for values in resources.chain(components) {
+        let ron::Value::Map(values) = values else {
+            continue;
+        };
+        for (type_path, value) in values.iter() {
+            let ron::Value::String(type_path) = type_path else {
+                continue;
+            };
+            let Some(registration) = registry.get_with_type_path(type_path) else {
+                mismatches.push(SchemaMismatch::Unregistered(type_path.clone()));
+                continue;
+            };
+            let (TypeInfo::Struct(info), ron::Value::Map(fields)) =
+                (registration.type_info(), value)
+            else {
+                continue;
+            };
+            let names = fields
+                .keys()
+                .filter_map(|name| match name {
+                    ron::Value::String(name) => Some(name.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            mismatches.extend(
+                names
+                    .iter()
+                    .filter(|&&name| info.field(name).is_none())
+                    .map(|&name| SchemaMismatch::UnknownField {
+                        type_path: type_path.clone(),
+                        field: name.to_string(),
+                    }),
+            );
+            mismatches.extend(
+                info.field_names()
+                    .iter()
+                    .filter(|name| !names.contains(name))
+                    .map(|&name| SchemaMismatch::MissingField {
+                        type_path: type_path.clone(),
+                        field: name.to_string(),
+                    }),
+            );
+        }
+    }
+    mismatches
+}
+
+/// Deserializes a scene from RON.
+/// Blank input is treated as an empty scene.
+fn scene_from_ron(type_registry: &AppTypeRegistry, ron: &str) -> Result<DynamicScene, LoadError> {
+    if ron.trim().is_empty() {
+        return Ok(DynamicScene::default());
+    }
+    let mut deserializer = ron::de::Deserializer::from_str(ron)?;
+    let scene = SceneDeserializer {
+        type_registry: &type_registry.read(),
+    }
+    .deserialize(&mut deserializer)?;
+    Ok(scene)
+}
+
+/// Reserves space for `additional` entities in `world`.
+fn reserve_entities(world: &mut World, additional: u32) {
+    // SAFETY: reserving only grows the allocation, so the entities stay valid.
+    unsafe { world.entities_mut() }.reserve(additional);
+}
+
+/// Extracts all entities and all resources allowed by `resource_filter` of `world` into a scene, entities are ordered by their id.
+fn scene_from_world(world: &World, resource_filter: &SceneFilter) -> DynamicScene {
+    let mut scene = DynamicSceneBuilder::from_world(world)
+        .with_resource_filter(resource_filter.clone())
+        .extract_entities(world.iter_entities().map(|entity_ref| entity_ref.id()))
+        .extract_resources()
+        .build();
+    scene.entities.sort_by_key(|entity| entity.entity);
+    scene
+}
+
+/// Collects the reflection data of all components of `entity` that is required to copy them, ordered by their type path.
+/// The stable order makes copies deterministic, independent of the component ids in `world`.
+/// Components that are not rust types are skipped with a warning, unless `strict` is set.
+/// Components for which `filter` returns `false` given their type and name are skipped as well.
+/// `world` has to contain an [AppTypeRegistry].
+fn reflect_components(
+    world: &World,
+    entity: Entity,
+    strict: bool,
+    filter: &dyn Fn(TypeId, &str) -> bool,
+) -> Result<Vec<ReflectComponent>, TransferError> {
+    let entity_ref = world
+        .get_entity(entity)
+        .ok_or(TransferError::Missing(entity))?;
+    let components = world.components();
+    // SAFETY: constructor guaranties that a `AppTypeRegistry` is added.
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut reflect_components = entity_ref
+        .archetype()
+        .components()
+        .filter_map(|component_id| {
+            let info = components
+                .get_info(component_id)
+                .expect("Component of an existing entity should be initialized");
+            let name = info.name().to_string();
+            let Some(type_id) = info.type_id() else {
+                let err = TransferError::NotRustType { component_id, name };
+                if strict {
+                    return Some(Err(err));
+                }
+                warn!("skipped component: {err}");
+                return None;
+            };
+            if !filter(type_id, &name) {
+                return None;
+            }
+            let Some(registration) = registry.get(type_id) else {
+                return Some(Err(TransferError::Unregistered {
+                    component_id,
+                    type_id,
+                    name,
+                }));
+            };
+            let Some(reflect_component) = registration.data::<ReflectComponent>().cloned() else {
+                return Some(Err(TransferError::NotReflectComponent {
+                    component_id,
+                    type_id,
+                    name,
+                }));
+            };
+            Some(Ok((
+                registration.type_info().type_path(),
+                reflect_component,
+            )))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    reflect_components.sort_unstable_by_key(|(type_path, _)| *type_path);
+    Ok(reflect_components
+        .into_iter()
+        .map(|(_, reflect_component)| reflect_component)
+        .collect())
+}
+
+/// Owned copies of components together with their reflection data.
+type ReflectedComponents = Vec<(ReflectComponent, Box<dyn Reflect>)>;
+
+/// Collects owned copies of all components of `entity` together with their reflection data.
+/// `world` has to contain an [AppTypeRegistry].
+fn reflect_values(
+    world: &World,
+    entity: Entity,
+    strict: bool,
+) -> Result<ReflectedComponents, TransferError> {
+    let entity_ref = world
+        .get_entity(entity)
+        .ok_or(TransferError::Missing(entity))?;
+    Ok(reflect_components(world, entity, strict, &|_, _| true)?
+        .into_iter()
+        .filter_map(|reflect_component| {
+            let value = reflect_component.reflect(entity_ref)?.clone_value();
+            Some((reflect_component, value))
+        })
+        .collect())
+}
+
+/// Hashes the id and archetype of every entity in `world`, so spawning, despawning and adding or removing components changes the result.
+fn static_checksum(world: &World) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for entity_ref in world.iter_entities() {
+        entity_ref.id().hash(&mut hasher);
+        entity_ref.archetype().id().index().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Estimates the size of all components stored in `world` in bytes, using the memory layout of each archetype.
+fn estimate_component_bytes(world: &World) -> usize {
+    let components = world.components();
+    world
+        .archetypes()
+        .iter()
+        .map(|archetype| {
+            let entity_size = archetype
+                .components()
+                .filter_map(|component_id| components.get_info(component_id))
+                .map(|info| info.layout().size())
+                .sum::<usize>();
+            entity_size * archetype.len()
+        })
+        .sum()
+}
+
+/// Returns `true` if each entity of `world` can be matched to an entity of `other` with equal reflected components.
+fn world_content_eq(world: &World, other: &World) -> bool {
+    let entities = |world: &World| {
+        world
+            .iter_entities()
+            .map(|entity_ref| reflect_values(world, entity_ref.id(), false))
+            .collect::<Result<Vec<_>, _>>()
+            .inspect_err(|err| warn!("{err}"))
+    };
+    let (Ok(entities), Ok(mut others)) = (entities(world), entities(other)) else {
+        return false;
+    };
+    if entities.len() != others.len() {
+        return false;
+    }
+    entities.iter().all(|components| {
+        let matching = others.iter().position(|other_components| {
+            components.len() == other_components.len()
+                && components
+                    .iter()
+                    .zip(other_components)
+                    .all(|((_, a), (_, b))| {
+                        a.reflect_type_path() == b.reflect_type_path()
+                            && a.reflect_partial_eq(b.as_ref()).unwrap_or(false)
+                    })
+        });
+        matching.map(|index| others.swap_remove(index)).is_some()
+    })
+}
+
+/// Writes `scene` into `world`, spawning all entities with the same ids they have in the scene.
+/// `world` should not contain any entities yet.
+/// Returns a map from the ids in the scene to the ids in `world`, which only differ when an id could not be reserved.
+fn write_scene_preserving_ids(
+    world: &mut World,
+    scene: &DynamicScene,
+) -> Result<EntityHashMap<Entity>, SceneSpawnError> {
+    let mut entity_map = EntityHashMap::default();
+    for entity in &scene.entities {
+        if world.get_or_spawn(entity.entity).is_some() {
+            entity_map.insert(entity.entity, entity.entity);
+        }
+    }
+    scene.write_to_world(world, &mut entity_map)?;
+    Ok(entity_map)
+}
+
+/// Creates a deep copy of `scene`.
+fn clone_scene(scene: &DynamicScene) -> DynamicScene {
+    DynamicScene {
+        resources: scene
+            .resources
+            .iter()
+            .map(|resource| resource.clone_value())
+            .collect(),
+        entities: scene
+            .entities
+            .iter()
+            .map(|entity| DynamicEntity {
+                entity: entity.entity,
+                components: entity
+                    .components
+                    .iter()
+                    .map(|component| component.clone_value())
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+/// Copies `components` of `source` in `source_world` onto `target` in `target_world`.
+/// `source_world` has to contain an [AppTypeRegistry].
+fn copy_entity(
+    components: &[ReflectComponent],
+    source_world: &World,
+    target_world: &mut World,
+    source: Entity,
+    target: Entity,
+) {
+    let registry = source_world.resource::<AppTypeRegistry>().read();
+    for reflect_component in components {
+        reflect_component.copy(source_world, target_world, source, target, &registry);
+    }
+}
+
+/// Replaces every [DataRef] stored in any component of `entity` with the result of `f`.
+fn map_entity_refs(world: &mut World, entity: Entity, f: &mut dyn FnMut(DataRef) -> DataRef) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry_guard = registry.read();
+    let type_ids = world
+        .entity(entity)
+        .archetype()
+        .components()
+        .filter_map(|component_id| world.components().get_info(component_id)?.type_id())
+        .collect::<Vec<_>>();
+    let mut entity_mut = world.entity_mut(entity);
+    for type_id in type_ids {
+        let Some(reflect_component) = registry_guard
+            .get(type_id)
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        else {
+            continue;
+        };
+        if let Some(mut component) = reflect_component.reflect_mut(&mut entity_mut) {
+            map_refs(&mut *component, f);
+        }
+    }
+}
+
+/// Replaces every [DataRef] nested inside `value` with the result of `f`.
+fn map_refs(value: &mut dyn Reflect, f: &mut dyn FnMut(DataRef) -> DataRef) {
+    if let Some(ptr) = value.downcast_mut::<DataRef>() {
+        *ptr = f(*ptr);
+        return;
+    }
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for index in 0..value.field_len() {
+                map_refs(value.field_at_mut(index).unwrap(), f);
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for index in 0..value.field_len() {
+                map_refs(value.field_mut(index).unwrap(), f);
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for index in 0..value.field_len() {
+                map_refs(value.field_mut(index).unwrap(), f);
+            }
+        }
+        ReflectMut::List(value) => {
+            for index in 0..value.len() {
+                map_refs(value.get_mut(index).unwrap(), f);
+            }
+        }
+        ReflectMut::Array(value) => {
+            for index in 0..value.len() {
+                map_refs(value.get_mut(index).unwrap(), f);
+            }
+        }
+        ReflectMut::Map(value) => {
+            for index in 0..value.len() {
+                map_refs(value.get_at_mut(index).unwrap().1, f);
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for index in 0..value.field_len() {
+                map_refs(value.field_at_mut(index).unwrap(), f);
+            }
+        }
+        ReflectMut::Value(_) => {}
+    }
+}
+
+/// Returns the names of all components that differ between `source` and `target`, sorted by name.
+/// Only components accepted by `filter` are compared.
+fn diff_components(
+    source_world: &World,
+    source: EntityRef,
+    target_world: &World,
+    target: EntityRef,
+    filter: &dyn Fn(TypeId, &str) -> bool,
+) -> Vec<String> {
+    // SAFETY: constructor guaranties that a `AppTypeRegistry` is added.
+    let registry = source_world.resource::<AppTypeRegistry>().read();
+    let component_types = |world: &World, entity: EntityRef| {
+        entity
+            .archetype()
+            .components()
+            .filter_map(|component_id| {
+                let info = world.components().get_info(component_id)?;
+                Some((info.type_id()?, info.name().to_string()))
+            })
+            .filter(|(type_id, name)| filter(*type_id, name))
+            .collect::<HashMap<_, _>>()
+    };
+    let source_types = component_types(source_world, source);
+    let target_types = component_types(target_world, target);
+    let mut changed = source_types
+        .iter()
+        .filter(|(type_id, _)| {
+            if !target_types.contains_key(type_id) {
+                return true;
+            }
+            let Some(reflect_component) = registry
+                .get(**type_id)
+                .and_then(|registration| registration.data::<ReflectComponent>())
+            else {
+                return false;
+            };
+            match (
+                reflect_component.reflect(source),
+                reflect_component.reflect(target),
+            ) {
+                (Some(a), Some(b)) => a.reflect_partial_eq(b) != Some(true),
+                _ => true,
+            }
+        })
+        .chain(
+            target_types
+                .iter()
+                .filter(|(type_id, _)| !source_types.contains_key(type_id)),
+        )
+        .map(|(_, name)| name.clone())
+        .collect::<Vec<_>>();
+    changed.sort_unstable();
+    changed
+}
+
+/// Calls `f` for every [DataRef] stored in any component of `entity`.
+fn visit_entity_refs(world: &World, entity: EntityRef, f: &mut dyn FnMut(DataRef)) {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    for component_id in entity.archetype().components() {
+        let Some(reflect_component) = world
+            .components()
+            .get_info(component_id)
+            .and_then(|info| info.type_id())
+            .and_then(|type_id| registry.get(type_id))
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        else {
+            continue;
+        };
+        if let Some(component) = reflect_component.reflect(entity) {
+            visit_refs(component, f);
+        }
+    }
+}
+
+/// Calls `f` for every [DataRef] nested inside `value`.
+fn visit_refs(value: &dyn Reflect, f: &mut dyn FnMut(DataRef)) {
+    if let Some(ptr) = value.downcast_ref::<DataRef>() {
+        f(*ptr);
+        return;
+    }
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            for index in 0..value.field_len() {
+                visit_refs(value.field_at(index).unwrap(), f);
+            }
+        }
+        ReflectRef::TupleStruct(value) => {
+            for index in 0..value.field_len() {
+                visit_refs(value.field(index).unwrap(), f);
+            }
+        }
+        ReflectRef::Tuple(value) => {
+            for index in 0..value.field_len() {
+                visit_refs(value.field(index).unwrap(), f);
+            }
+        }
+        ReflectRef::List(value) => {
+            for item in value.iter() {
+                visit_refs(item, f);
+            }
+        }
+        ReflectRef::Array(value) => {
+            for item in value.iter() {
+                visit_refs(item, f);
+            }
+        }
+        ReflectRef::Map(value) => {
+            for (_, item) in value.iter() {
+                visit_refs(item, f);
+            }
+        }
+        ReflectRef::Enum(value) => {
+            for field in value.iter_fields() {
+                visit_refs(field.value(), f);
+            }
+        }
+        ReflectRef::Value(_) => {}
+    }
+}
+
+/// Reference to data stored in [DataWorlds].
+///
+/// # Safety
+/// For data that is static but might be mutabe at a later point all cross references should be `DataRef` instead of plain [Entity] fields,
+/// as those would get invalidated when the data gets transfered to the dynamic world.
+///
+/// References are serialized in a compact string form: `null`, `static:<index>v<generation>` or `dynamic:<layer>:<index>v<generation>`.
+#[derive(Debug, Reflect, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Default, PartialEq, Hash, Serialize, Deserialize)]
+pub enum DataRef {
+    /// Null pointer.
+    #[default]
+    Null,
+    /// Data located in the static world.
+    Static(Entity),
+    /// Data located in a dynamic layer.
+    Dynamic(LayerId, Entity),
+}
+impl DataRef {
+    /// Returns a [`Null`](DataRef::Null) reference.
+    #[inline]
+    pub const fn null() -> Self {
+        Self::Null
+    }
+    /// Returns `true` if both references point to the same data after mapping them using `resolve`.
+    /// Use [`DataWorlds::resolve`] to treat static data and its transferred dynamic copy as equal.
+    #[inline]
+    pub fn same_target(&self, other: &DataRef, resolve: impl Fn(DataRef) -> DataRef) -> bool {
+        resolve(*self) == resolve(*other)
+    }
+    /// Returns `true` if this is a [`Null`](DataRef::Null) reference.
+    #[inline]
+    pub const fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+    /// Returns `true` if this references [`Static`](DataRef::Static) data.
+    #[inline]
+    pub const fn is_static(&self) -> bool {
+        matches!(self, Self::Static(_))
+    }
+    /// Returns `true` if this references [`Dynamic`](DataRef::Dynamic) data.
+    #[inline]
+    pub const fn is_dynamic(&self) -> bool {
+        matches!(self, Self::Dynamic(..))
+    }
+    /// Returns the referenced entity, or [`None`] for [`Null`](DataRef::Null) references.
+    #[inline]
+    pub const fn entity(&self) -> Option<Entity> {
+        match self {
+            Self::Null => None,
+            Self::Static(entity) | Self::Dynamic(_, entity) => Some(*entity),
+        }
+    }
+}
+
+/// Translates `ptr`, produced by a different [DataWorlds], by mapping its entity using `remap`.
+/// The variant and layer are kept, [`Null`](DataRef::Null) references and entities missing from `remap` are returned unchanged.
+pub fn rebase_ref(ptr: DataRef, remap: &HashMap<Entity, Entity>) -> DataRef {
+    match ptr {
+        DataRef::Static(entity) => DataRef::Static(remap.get(&entity).copied().unwrap_or(entity)),
+        DataRef::Dynamic(layer, entity) => {
+            DataRef::Dynamic(layer, remap.get(&entity).copied().unwrap_or(entity))
+        }
+        DataRef::Null => DataRef::Null,
+    }
+}
+impl fmt::Display for DataRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Static(entity) => write!(f, "static:{}v{}", entity.index(), entity.generation()),
+            Self::Dynamic(layer, entity) => write!(
+                f,
+                "dynamic:{}:{}v{}",
+                layer.0,
+                entity.index(),
+                entity.generation()
+            ),
+        }
+    }
+}
+impl FromStr for DataRef {
+    type Err = ParseDataRefError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseDataRefError(s.to_string());
+        let parse_entity = |entity: &str| {
+            let (index, generation) = entity.split_once('v')?;
+            let index = index.parse::<u32>().ok()?;
+            let generation = generation.parse::<u32>().ok()?;
+            Entity::try_from_bits((generation as u64) << 32 | index as u64).ok()
+        };
+        if s == "null" {
+            return Ok(Self::Null);
+        }
+        if let Some(entity) = s.strip_prefix("static:") {
+            return parse_entity(entity).map(Self::Static).ok_or_else(err);
+        }
+        let (layer, entity) = s
+            .strip_prefix("dynamic:")
+            .and_then(|s| s.split_once(':'))
+            .ok_or_else(err)?;
+        let layer = LayerId(layer.parse().map_err(|_| err())?);
+        parse_entity(entity)
+            .map(|entity| Self::Dynamic(layer, entity))
+            .ok_or_else(err)
+    }
+}
+impl Serialize for DataRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+impl<'de> Deserialize<'de> for DataRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+/// Kind of world data is stored in, see [`entity_location`](DataWorlds::entity_location).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorldKind {
+    /// The static world.
+    Static,
+    /// One of the dynamic layers.
+    Dynamic,
+}
+
+/// Identifier of a dynamic data layer in [DataWorlds].
+#[derive(Debug, Reflect, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[reflect(Default, PartialEq, Hash)]
+pub struct LayerId(u32);
+impl LayerId {
+    /// The bottom-most layer, which always exists.
+    pub const BASE: Self = Self(0);
+}
+
+#[cfg(test)]
+mod test {
+    use std::alloc::Layout;
+
+    use bevy_ecs::{
+        component::{ComponentDescriptor, StorageType},
+        ptr::OwningPtr,
+    };
+
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, Reflect, Component)]
+    #[reflect(Component)]
+    struct SomeCompoennt {
+        data: i32,
+    }
+    #[derive(Debug, Clone, Copy, PartialEq, Reflect, Component)]
+    #[reflect(Component)]
+    struct SomeRef {
+        entity: DataRef,
+    }
+    fn setup_data(world: &mut World) -> DataRef {
+        let a = world.spawn(SomeCompoennt { data: 42 }).id();
+        let b = world
+            .spawn((
+                SomeCompoennt { data: 21 },
+                SomeRef {
+                    entity: DataRef::Static(a),
+                },
+            ))
+            .id();
+        DataRef::Static(b)
+    }
+    fn type_registry() -> AppTypeRegistry {
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut guard = type_registry.write();
+            guard.register::<Entity>();
+            guard.register::<DataRef>();
+            guard.register::<LayerId>();
+            guard.register::<SomeCompoennt>();
+            guard.register::<SomeRef>();
+        }
+        type_registry
+    }
+
+    #[test]
+    fn test() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let root = data.modify_static_data(setup_data);
+        let mut world = World::new();
+        world.insert_resource(type_registry);
+        world.insert_resource(data);
+        {
+            let data = world.resource_ref::<DataWorlds>();
+            let b = data.entity(root);
+            assert_eq!(b.get::<SomeCompoennt>().unwrap().data, 21);
+            let a = data.entity(b.get::<SomeRef>().unwrap().entity);
+            assert_eq!(a.get::<SomeCompoennt>().unwrap().data, 42);
+        }
+        {
             let mut data = world.resource_mut::<DataWorlds>();
-            let DataMut::Moved(mut b, entity) = data.entity_mut(root) else {
+            let DataMut::Moved {
+                entity: mut b,
+                old,
+                new: entity,
+            } = data.entity_mut(root)
+            else {
                 panic!()
             };
-            b.get_mut::<SomeCompoennt>().unwrap().data = 42;
-            let b = data.entity(entity);
-            assert_eq!(b.get::<SomeCompoennt>().unwrap().data, 42);
+            assert_eq!(old, root);
+            b.get_mut::<SomeCompoennt>().unwrap().data = 42;
+            let b = data.entity(entity);
+            assert_eq!(b.get::<SomeCompoennt>().unwrap().data, 42);
+            assert_eq!(data.resolve(root), entity);
+            let b = data.entity(root);
+            assert_eq!(b.get::<SomeCompoennt>().unwrap().data, 42);
+        }
+        let _data = {
+            let data = world.resource::<DataWorlds>();
+            let static_ron = data.serialize_static_ron().unwrap();
+            let dynamic_ron = data.serialize_dynamic_ron().unwrap();
+            (static_ron, dynamic_ron)
+        };
+        world.remove_resource::<DataWorlds>();
+    }
+
+    #[test]
+    fn transfer_once() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let DataMut::Moved {
+            entity: mut b,
+            new: first,
+            ..
+        } = data.get_mut(root)
+        else {
+            panic!()
+        };
+        b.get_mut::<SomeCompoennt>().unwrap().data = 7;
+        let DataMut::Moved {
+            entity: b,
+            new: second,
+            ..
+        } = data.get_mut(root)
+        else {
+            panic!()
+        };
+        assert_eq!(b.get::<SomeCompoennt>().unwrap().data, 7);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn save_and_load_file() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let root = data.modify_static_data(setup_data);
+        let DataMut::Moved { entity: mut b, .. } = data.get_mut(root) else {
+            panic!()
+        };
+        b.get_mut::<SomeCompoennt>().unwrap().data = 13;
+        let path = std::env::temp_dir().join("data_world_save_and_load_file.ron");
+        data.save_dynamic_to_file(&path).unwrap();
+
+        let mut loaded = DataWorlds::from_scenes(&type_registry, None, None);
+        loaded
+            .load_dynamic_from_file(&type_registry, &path)
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+        let dynamic_world = loaded.dynamic_world_mut();
+        let mut query = dynamic_world.query::<&SomeCompoennt>();
+        let values = query
+            .iter(dynamic_world)
+            .map(|component| component.data)
+            .collect::<Vec<_>>();
+        assert_eq!(values, [13]);
+    }
+
+    #[test]
+    fn try_access() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        assert!(data.try_entity(root).is_ok());
+        assert_eq!(
+            data.try_entity(DataRef::Null).err(),
+            Some(DataError::NullReference)
+        );
+        let DataMut::Moved { new: moved, .. } = data.try_entity_mut(root).unwrap() else {
+            panic!()
+        };
+        assert!(matches!(data.try_entity_mut(moved), Ok(DataMut::Found(_))));
+        let missing = DataRef::Dynamic(LayerId::BASE, Entity::from_raw(42));
+        assert_eq!(
+            data.try_entity(missing).err(),
+            Some(DataError::Missing(missing))
+        );
+        assert!(matches!(
+            data.try_entity_mut(DataRef::Null),
+            Err(DataError::NullReference)
+        ));
+        assert!(matches!(
+            data.try_entity_mut(missing),
+            Err(DataError::Missing(_))
+        ));
+    }
+
+    #[test]
+    fn commit_dynamic() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let DataMut::Moved {
+            entity: mut b,
+            new: moved,
+            ..
+        } = data.get_mut(root)
+        else {
+            panic!()
+        };
+        b.get_mut::<SomeCompoennt>().unwrap().data = 7;
+        let DataRef::Dynamic(_, moved) = moved else {
+            panic!()
+        };
+        let new = data
+            .dynamic_world_mut()
+            .spawn((
+                SomeCompoennt { data: 3 },
+                SomeRef {
+                    entity: DataRef::Dynamic(LayerId::BASE, moved),
+                },
+            ))
+            .id();
+        data.commit_dynamic_to_static().unwrap();
+        assert_eq!(data.resolve(root), root);
+        assert!(data.dynamic_world().get_entity(moved).is_none());
+        assert!(data.dynamic_world().get_entity(new).is_none());
+        let b = data.entity(root);
+        assert_eq!(b.get::<SomeCompoennt>().unwrap().data, 7);
+        let mut query = data.static_world.query::<(&SomeCompoennt, &SomeRef)>();
+        let (_, new_ref) = query
+            .iter(&data.static_world)
+            .find(|(component, _)| component.data == 3)
+            .unwrap();
+        assert_eq!(new_ref.entity, root);
+    }
+
+    #[test]
+    fn layers() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let a = data.entity(root).get::<SomeRef>().unwrap().entity;
+        let DataMut::Moved { new: moved_a, .. } = data.get_mut(a) else {
+            panic!()
+        };
+        assert!(matches!(moved_a, DataRef::Dynamic(LayerId::BASE, _)));
+        let layer = data.push_layer();
+        assert_eq!(data.active_layer(), layer);
+        let DataMut::Moved {
+            entity: mut b,
+            new: moved_b,
+            ..
+        } = data.get_mut(root)
+        else {
+            panic!()
+        };
+        b.get_mut::<SomeCompoennt>().unwrap().data = 7;
+        assert!(matches!(moved_b, DataRef::Dynamic(target, _) if target == layer));
+        assert_eq!(data.resolve(a), moved_a);
+        assert_eq!(data.entity(root).get::<SomeCompoennt>().unwrap().data, 7);
+        assert!(data.pop_layer().is_some());
+        assert_eq!(data.active_layer(), LayerId::BASE);
+        assert!(data.pop_layer().is_none());
+        assert_eq!(data.resolve(root), root);
+        assert!(data.get(moved_b).is_none());
+        assert_eq!(data.entity(root).get::<SomeCompoennt>().unwrap().data, 21);
+        assert_eq!(data.resolve(a), moved_a);
+    }
+
+    #[test]
+    fn change_detection() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let dynamic_world = data.dynamic_world_mut();
+        let a = dynamic_world.spawn(SomeCompoennt { data: 1 }).id();
+        let b = dynamic_world.spawn(SomeCompoennt { data: 2 }).id();
+        let tick = data.last_change_tick();
+        assert!(data.changed_since(tick).is_empty());
+        let DataMut::Found(mut a_mut) = data.get_mut(DataRef::Dynamic(LayerId::BASE, a)) else {
+            panic!()
+        };
+        a_mut.get_mut::<SomeCompoennt>().unwrap().data = 3;
+        assert_eq!(data.changed_since(tick), [a]);
+        assert!(!data.changed_since(tick).contains(&b));
+    }
+
+    #[test]
+    fn despawn() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let entity = data
+            .dynamic_world_mut()
+            .spawn(SomeCompoennt { data: 1 })
+            .id();
+        let ptr = DataRef::Dynamic(LayerId::BASE, entity);
+        assert_eq!(data.get_or_null(ptr), ptr);
+        assert!(data.despawn(ptr).unwrap());
+        assert!(data.get(ptr).is_none());
+        assert_eq!(data.get_or_null(ptr), DataRef::Null);
+        assert!(!data.despawn(ptr).unwrap());
+
+        assert!(!data.despawn(root).unwrap());
+        let DataMut::Moved { new: moved, .. } = data.get_mut(root) else {
+            panic!()
+        };
+        assert!(data.despawn(moved).unwrap());
+        assert_eq!(data.resolve(root), root);
+        assert_eq!(data.entity(root).get::<SomeCompoennt>().unwrap().data, 21);
+    }
+
+    #[test]
+    fn component_access() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 21);
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 7;
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 7);
+        let moved = data.resolve(root);
+        assert!(moved.is_dynamic());
+        data.get_component_mut::<SomeCompoennt>(moved).unwrap().data = 8;
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 8);
+        assert!(data.get_component::<SomeCompoennt>(DataRef::Null).is_none());
+        assert!(data
+            .get_component_mut::<SomeCompoennt>(DataRef::Null)
+            .is_none());
+    }
+
+    #[test]
+    fn transfer_recursive() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let (a, b) = data.modify_static_data(|world: &mut World| {
+            let a = world.spawn(SomeCompoennt { data: 1 }).id();
+            let b = world
+                .spawn((
+                    SomeCompoennt { data: 2 },
+                    SomeRef {
+                        entity: DataRef::Static(a),
+                    },
+                ))
+                .id();
+            world.entity_mut(a).insert(SomeRef {
+                entity: DataRef::Static(b),
+            });
+            (DataRef::Static(a), DataRef::Static(b))
+        });
+        let moved_a = data.transfer_recursive(a);
+        let moved_b = data.resolve(b);
+        assert!(moved_a.is_dynamic());
+        assert!(moved_b.is_dynamic());
+        assert_eq!(
+            data.get_component::<SomeRef>(moved_a).unwrap().entity,
+            moved_b
+        );
+        assert_eq!(
+            data.get_component::<SomeRef>(moved_b).unwrap().entity,
+            moved_a
+        );
+        let missing = "dynamic:0:5v1".parse::<DataRef>().unwrap();
+        assert_eq!(data.transfer_recursive(missing), DataRef::Null);
+    }
+
+    #[test]
+    fn data_ref_predicates() {
+        let entity = Entity::from_raw(1);
+        let null = DataRef::Null;
+        assert!(null.is_null() && !null.is_static() && !null.is_dynamic());
+        assert_eq!(null.entity(), None);
+        let static_ref = DataRef::Static(entity);
+        assert!(!static_ref.is_null() && static_ref.is_static() && !static_ref.is_dynamic());
+        assert_eq!(static_ref.entity(), Some(entity));
+        let dynamic_ref = DataRef::Dynamic(LayerId::BASE, entity);
+        assert!(!dynamic_ref.is_null() && !dynamic_ref.is_static() && dynamic_ref.is_dynamic());
+        assert_eq!(dynamic_ref.entity(), Some(entity));
+    }
+
+    #[test]
+    fn validate_references() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let root = data.modify_static_data(setup_data);
+        let broken = DataRef::Static(Entity::from_raw(100));
+        data.dynamic_world_mut().spawn(SomeRef { entity: broken });
+        data.dynamic_world_mut().spawn(SomeRef { entity: root });
+        let path = std::env::temp_dir().join("data_world_validate_references.ron");
+        data.save_dynamic_to_file(&path).unwrap();
+
+        let mut loaded = DataWorlds::from_scenes(&type_registry, None, None);
+        loaded.modify_static_data(setup_data);
+        loaded
+            .load_dynamic_from_file(&type_registry, &path)
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+        let dangling = loaded.validate_references();
+        assert_eq!(dangling.len(), 1);
+        let (holder, target) = dangling[0];
+        assert!(holder.is_dynamic());
+        assert_eq!(target, broken);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let root = data.modify_static_data(setup_data);
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 5;
+        data.dynamic_world_mut().spawn(SomeCompoennt { data: 6 });
+        let json = data.serialize_dynamic_json().unwrap();
+        assert_eq!(json, data.serialize_dynamic_json().unwrap());
+
+        let mut loaded = DataWorlds::from_scenes(&type_registry, None, None);
+        loaded
+            .load_dynamic_from_json(&type_registry, &json)
+            .unwrap();
+        let dynamic_world = loaded.dynamic_world_mut();
+        let mut query = dynamic_world.query::<&SomeCompoennt>();
+        let mut values = query
+            .iter(dynamic_world)
+            .map(|component| component.data)
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, [5, 6]);
+        assert_eq!(json, loaded.serialize_dynamic_json().unwrap());
+    }
+
+    #[test]
+    fn iterate() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(|world: &mut World| {
+            world.spawn(SomeCompoennt { data: 3 });
+            setup_data(world)
+        });
+        let mut values = data
+            .iter_static()
+            .map(|(ptr, entity_ref)| {
+                assert!(ptr.is_static());
+                assert_eq!(ptr.entity(), Some(entity_ref.id()));
+                entity_ref.get::<SomeCompoennt>().unwrap().data
+            })
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, [3, 21, 42]);
+        assert_eq!(data.iter_dynamic().count(), 0);
+        data.get_mut(root);
+        let dynamic = data.iter_dynamic().map(|(ptr, _)| ptr).collect::<Vec<_>>();
+        assert_eq!(dynamic, [data.resolve(root)]);
+    }
+
+    #[test]
+    fn find() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let [x, y] = data.modify_static_data(|world: &mut World| {
+            [3, 3].map(|value| DataRef::Static(world.spawn(SomeCompoennt { data: value }).id()))
+        });
+        assert_eq!(data.find::<SomeCompoennt>(|c| c.data == 21), Some(root));
+        assert_eq!(data.find::<SomeCompoennt>(|c| c.data == 0), None);
+        let mut all = data.find_all::<SomeCompoennt>(|c| c.data == 3);
+        all.sort_by_key(|ptr| ptr.entity());
+        assert_eq!(all, [x, y]);
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 0;
+        assert_eq!(data.find::<SomeCompoennt>(|c| c.data == 21), None);
+        assert_eq!(
+            data.find::<SomeCompoennt>(|c| c.data == 0),
+            Some(data.resolve(root))
+        );
+    }
+
+    #[test]
+    fn transfer_unregistered() {
+        #[derive(Component)]
+        struct Unregistered;
+
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let ptr = data.modify_static_data(|world: &mut World| {
+            DataRef::Static(world.spawn((SomeCompoennt { data: 1 }, Unregistered)).id())
+        });
+        let Err(DataError::Transfer(err)) = data.try_entity_mut(ptr) else {
+            panic!()
+        };
+        assert!(
+            matches!(err, TransferError::Unregistered { type_id, .. } if type_id == TypeId::of::<Unregistered>())
+        );
+        assert!(err.to_string().contains("Unregistered"));
+        assert!(matches!(data.get_mut(ptr), DataMut::Missing));
+        assert_eq!(data.resolve(ptr), ptr);
+        assert_eq!(data.iter_dynamic().count(), 0);
+    }
+
+    #[test]
+    fn clone_dynamic() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let moved = data.transfer_recursive(root);
+        let clone = data.clone_dynamic(moved);
+        assert!(clone.is_dynamic());
+        assert_ne!(clone, moved);
+        data.get_component_mut::<SomeCompoennt>(clone).unwrap().data = 1;
+        assert_eq!(data.get_component::<SomeCompoennt>(moved).unwrap().data, 21);
+        assert_eq!(data.get_component::<SomeCompoennt>(clone).unwrap().data, 1);
+        assert_eq!(
+            data.get_component::<SomeRef>(clone).unwrap().entity,
+            data.get_component::<SomeRef>(moved).unwrap().entity
+        );
+        assert_eq!(data.clone_dynamic(DataRef::Null), DataRef::Null);
+        assert!(data.despawn_forced(clone));
+        assert_eq!(data.clone_dynamic(clone), DataRef::Null);
+    }
+
+    #[test]
+    fn counts() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        assert_eq!(data.static_entity_count(), 2);
+        assert_eq!(data.dynamic_entity_count(), 0);
+        assert_eq!(data.transferred_count(), 0);
+        data.get_mut(root);
+        data.get_mut(root);
+        assert_eq!(data.static_entity_count(), 2);
+        assert_eq!(data.dynamic_entity_count(), 1);
+        assert_eq!(data.transferred_count(), 1);
+    }
+
+    #[test]
+    fn reset_to_static() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        assert!(!data.reset_entity_to_static(root));
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 1;
+        let moved = data.resolve(root);
+        assert!(data.reset_entity_to_static(moved));
+        assert!(data.get(moved).is_none());
+        assert_eq!(data.resolve(root), root);
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 21);
+        let new = data.clone_dynamic(root);
+        assert!(!data.reset_entity_to_static(new));
+        assert!(data.get(new).is_some());
+    }
+
+    #[test]
+    fn spawn_dynamic() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let ptr = data.spawn_dynamic(SomeCompoennt { data: 5 });
+        assert_eq!(ptr, DataRef::Dynamic(LayerId::BASE, ptr.entity().unwrap()));
+        assert_eq!(data.get_component::<SomeCompoennt>(ptr).unwrap().data, 5);
+        assert!(matches!(data.get_mut(ptr), DataMut::Found(_)));
+    }
+
+    #[test]
+    fn merge_dynamic_scene() {
+        let type_registry = type_registry();
+        let mut source = World::new();
+        source.insert_resource(type_registry.clone());
+        let a = source.spawn(SomeCompoennt { data: 1 }).id();
+        source.spawn(SomeRef {
+            entity: DataRef::Dynamic(LayerId::BASE, a),
+        });
+        let scene = DynamicScene::from_world(&source);
+
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let existing = data.spawn_dynamic(SomeCompoennt { data: 2 });
+        let merged = data.merge_dynamic_scene(scene).unwrap();
+        assert_eq!(merged.len(), 2);
+        assert_eq!(data.dynamic_entity_count(), 3);
+        assert_eq!(
+            data.get_component::<SomeCompoennt>(existing).unwrap().data,
+            2
+        );
+        let new_a = merged
+            .iter()
+            .copied()
+            .find(|&ptr| data.get_component::<SomeCompoennt>(ptr).is_some())
+            .unwrap();
+        assert_eq!(data.get_component::<SomeCompoennt>(new_a).unwrap().data, 1);
+        let holder = merged.iter().copied().find(|&ptr| ptr != new_a).unwrap();
+        assert_eq!(data.get_component::<SomeRef>(holder).unwrap().entity, new_a);
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let root = data.modify_static_data(setup_data);
+        for value in 0..100 {
+            data.spawn_dynamic((SomeCompoennt { data: value }, SomeRef { entity: root }));
+        }
+        let bytes = data.serialize_dynamic_bytes().unwrap();
+        let ron = data.serialize_dynamic_ron().unwrap();
+        assert!(bytes.len() < ron.len());
+
+        let mut loaded = DataWorlds::from_scenes(&type_registry, None, None);
+        loaded
+            .load_dynamic_from_bytes(&type_registry, &bytes)
+            .unwrap();
+        assert_eq!(loaded.serialize_dynamic_ron().unwrap(), ron);
+        assert!(matches!(
+            loaded.load_dynamic_from_bytes(&type_registry, &bytes[1..]),
+            Err(LoadError::InvalidHeader)
+        ));
+        let mut future = bytes.clone();
+        future[4] = 2;
+        assert!(matches!(
+            loaded.load_dynamic_from_bytes(&type_registry, &future),
+            Err(LoadError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn snapshot_restore() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let ptr = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let snapshot = data.snapshot();
+        let undo = snapshot.clone();
+        data.get_component_mut::<SomeCompoennt>(ptr).unwrap().data = 2;
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 3;
+        data.push_layer();
+        data.spawn_dynamic(SomeCompoennt { data: 4 });
+        data.restore(undo).unwrap();
+        assert_eq!(data.active_layer(), LayerId::BASE);
+        assert_eq!(data.get_component::<SomeCompoennt>(ptr).unwrap().data, 1);
+        assert_eq!(data.resolve(root), root);
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 21);
+        assert_eq!(data.dynamic_entity_count(), 1);
+        data.restore(snapshot).unwrap();
+        assert_eq!(data.get_component::<SomeCompoennt>(ptr).unwrap().data, 1);
+    }
+
+    #[test]
+    fn repair_references() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(root).unwrap().entity;
+        data.get_mut(root);
+        data.get_mut(a);
+        let moved_root = data.resolve(root);
+        assert_eq!(data.get_component::<SomeRef>(moved_root).unwrap().entity, a);
+        assert_eq!(data.repair_references(), 1);
+        assert_eq!(
+            data.get_component::<SomeRef>(moved_root).unwrap().entity,
+            data.resolve(a)
+        );
+        assert_eq!(data.repair_references(), 0);
+    }
+
+    #[test]
+    fn register_late() {
+        let type_registry = AppTypeRegistry::default();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        data.register::<SomeCompoennt>();
+        assert!(data
+            .type_registry()
+            .read()
+            .get(TypeId::of::<SomeCompoennt>())
+            .is_some());
+        let ptr = data.modify_static_data(|world: &mut World| {
+            DataRef::Static(world.spawn(SomeCompoennt { data: 1 }).id())
+        });
+        assert!(data
+            .serialize_static_ron()
+            .unwrap()
+            .contains("SomeCompoennt"));
+        data.get_component_mut::<SomeCompoennt>(ptr).unwrap().data = 2;
+        assert!(data
+            .serialize_dynamic_ron()
+            .unwrap()
+            .contains("SomeCompoennt"));
+    }
+
+    #[test]
+    fn plugin() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        data.modify_static_data(setup_data);
+        data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let static_path = std::env::temp_dir().join("data_world_plugin_static.ron");
+        let dynamic_path = std::env::temp_dir().join("data_world_plugin_dynamic.ron");
+        data.save_static_to_file(&static_path).unwrap();
+        data.save_dynamic_to_file(&dynamic_path).unwrap();
+
+        let mut app = App::new();
+        app.register_type::<SomeCompoennt>()
+            .register_type::<SomeRef>()
+            .add_plugins(DataWorldsPlugin {
+                static_path: Some(static_path.clone()),
+                dynamic_path: Some(dynamic_path.clone()),
+            });
+        app.finish();
+        fs::remove_file(static_path).unwrap();
+        fs::remove_file(dynamic_path).unwrap();
+        let data = app.world.resource::<DataWorlds>();
+        assert!(data
+            .type_registry()
+            .read()
+            .get(TypeId::of::<DataRef>())
+            .is_some());
+        assert_eq!(data.static_entity_count(), 2);
+        assert_eq!(data.dynamic_entity_count(), 1);
+        assert!(data.find::<SomeCompoennt>(|c| c.data == 42).is_some());
+    }
+
+    #[test]
+    fn get_many() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let dynamic = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let [a, b, c] = data.get_many([root, DataRef::Null, dynamic]);
+        assert_eq!(a.unwrap().get::<SomeCompoennt>().unwrap().data, 21);
+        assert!(b.is_none());
+        assert_eq!(c.unwrap().get::<SomeCompoennt>().unwrap().data, 1);
+    }
+
+    #[test]
+    fn skip_resources() {
+        #[derive(Debug, Default, Resource, Reflect)]
+        #[reflect(Resource)]
+        struct SomeResource(i32);
+
+        let type_registry = type_registry();
+        type_registry.write().register::<SomeResource>();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        data.spawn_dynamic(SomeCompoennt { data: 1 });
+        assert!(data
+            .serialize_dynamic_ron()
+            .unwrap()
+            .contains("resources: {}"));
+        data.dynamic_world_mut().insert_resource(SomeResource(2));
+        assert!(data
+            .serialize_dynamic_ron()
+            .unwrap()
+            .contains("SomeResource"));
+        data.skip_resource::<SomeResource>();
+        let ron = data.serialize_dynamic_ron().unwrap();
+        assert!(ron.contains("resources: {}"));
+        assert!(ron.contains("SomeCompoennt"));
+    }
+
+    #[test]
+    fn contains() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let entity = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        assert!(!data.contains(DataRef::Null));
+        assert!(data.contains(root));
+        assert!(data.contains(entity));
+        assert!(data.despawn(entity).unwrap());
+        assert!(!data.contains(entity));
+    }
+
+    #[test]
+    fn resolve_path() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(|world: &mut World| {
+            let a = world.spawn(SomeCompoennt { data: 1 }).id();
+            let b = world
+                .spawn(SomeRef {
+                    entity: DataRef::Static(a),
+                })
+                .id();
+            let c = world
+                .spawn(SomeRef {
+                    entity: DataRef::Static(b),
+                })
+                .id();
+            DataRef::Static(c)
+        });
+        let field = |entity: &EntityRef| entity.get::<SomeRef>().unwrap().entity;
+        let end = data.resolve_path::<SomeRef>(root, field, 5).unwrap();
+        assert_eq!(end.get::<SomeCompoennt>().unwrap().data, 1);
+        let middle = data.resolve_path::<SomeRef>(root, field, 1).unwrap();
+        assert!(middle.contains::<SomeRef>());
+        assert!(data
+            .resolve_path::<SomeRef>(DataRef::Null, field, 2)
+            .is_none());
+    }
+
+    #[test]
+    fn diff_against_static() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let DataRef::Static(source) = root else {
+            unreachable!()
+        };
+        data.get_component_mut::<SomeCompoennt>(root).unwrap();
+        assert!(data.diff_against_static().is_empty());
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 7;
+        let diff = data.diff_against_static();
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].source, source);
+        assert_eq!(diff[0].target, data.resolve(root));
+        assert_eq!(diff[0].changed.len(), 1);
+        assert!(diff[0].changed[0].ends_with("SomeCompoennt"));
+    }
+
+    #[test]
+    fn diff_against_static_skipped() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        data.skip_on_transfer::<SomeRef>();
+        data.get_component_mut::<SomeCompoennt>(root).unwrap();
+        assert!(data.diff_against_static().is_empty());
+    }
+
+    #[test]
+    fn builder() {
+        #[derive(Resource)]
+        struct SomeResource;
+
+        let data = DataWorlds::builder()
+            .type_registry(&type_registry())
+            .dynamic_scene(DynamicSceneBundle::default())
+            .skip_resource::<SomeResource>()
+            .build();
+        assert_eq!(data.static_entity_count(), 0);
+        assert_eq!(data.dynamic_entity_count(), 1);
+    }
+
+    #[test]
+    fn transfer_events() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let b = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(b).unwrap().entity;
+        data.get_component_mut::<SomeCompoennt>(a).unwrap();
+        data.get_component_mut::<SomeCompoennt>(b).unwrap();
+        data.get_component_mut::<SomeCompoennt>(b).unwrap();
+        let events = data.drain_transfer_events();
+        assert_eq!(
+            events,
+            vec![
+                DataTransferred {
+                    old: a,
+                    new: data.resolve(a)
+                },
+                DataTransferred {
+                    old: b,
+                    new: data.resolve(b)
+                },
+            ]
+        );
+        assert!(data.drain_transfer_events().is_empty());
+    }
+
+    #[test]
+    fn peek_mut() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        {
+            let peek = data.peek_mut(root);
+            assert_eq!(peek.get::<SomeCompoennt>().unwrap().data, 21);
+        }
+        assert_eq!(data.transferred_count(), 0);
+        {
+            let mut peek = data.peek_mut(root);
+            peek.get_mut::<SomeCompoennt>().unwrap().data = 1;
+            assert!(peek.is_transferred());
+        }
+        assert_eq!(data.transferred_count(), 1);
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 1);
+    }
+
+    #[test]
+    fn peek_mut_without_write() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        {
+            let mut peek = data.peek_mut(root);
+            let component = peek.get_mut::<SomeCompoennt>().unwrap();
+            assert_eq!(component.data, 21);
+        }
+        assert!(data.resolve(root).is_static());
+        assert_eq!(data.dynamic_entity_count(), 0);
+        assert!(data.drain_transfer_events().is_empty());
+    }
+
+    #[test]
+    fn remove_component() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        assert!(data.remove_component::<SomeRef>(root));
+        assert!(!data.remove_component::<SomeRef>(root));
+        assert!(!data.get(root).unwrap().contains::<SomeRef>());
+        assert!(data.get(root).unwrap().contains::<SomeCompoennt>());
+        assert!(data
+            .static_world()
+            .get::<SomeRef>(root.entity().unwrap())
+            .is_some());
+    }
+
+    #[test]
+    fn insert_component() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(root).unwrap().entity;
+        let new = data.insert_component(a, SomeRef { entity: root });
+        assert!(new.is_dynamic());
+        assert_eq!(new, data.resolve(a));
+        let entity = data.get(a).unwrap();
+        assert_eq!(entity.get::<SomeCompoennt>().unwrap().data, 42);
+        assert_eq!(entity.get::<SomeRef>().unwrap().entity, root);
+        assert!(!data
+            .insert_component(DataRef::Null, SomeCompoennt { data: 0 })
+            .is_dynamic());
+    }
+
+    #[test]
+    fn load_preserving_ids() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let a = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let b = data.spawn_dynamic(SomeCompoennt { data: 2 });
+        let c = data.spawn_dynamic(SomeRef { entity: b });
+        data.despawn(a).unwrap();
+        let path = std::env::temp_dir().join("data_world_load_preserving_ids.ron");
+        data.save_dynamic_to_file(&path).unwrap();
+        let mut loaded = DataWorlds::from_scenes(&type_registry, None, None);
+        let entity_map = loaded
+            .load_dynamic_from_file_preserving_ids(&type_registry, &path)
+            .unwrap();
+        fs::remove_file(path).unwrap();
+        assert_eq!(entity_map.len(), 2);
+        let DataRef::Dynamic(layer, entity) = c else {
+            unreachable!()
+        };
+        let c = DataRef::Dynamic(layer, entity_map[&entity]);
+        assert_eq!(c, DataRef::Dynamic(layer, entity));
+        let b = loaded.get_component::<SomeRef>(c).unwrap().entity;
+        assert_eq!(loaded.get_component::<SomeCompoennt>(b).unwrap().data, 2);
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict-null"))]
+    fn entity_or_else() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let missing = data.spawn_dynamic(());
+        data.despawn(missing).unwrap();
+        let fallback = || data.entity(root);
+        assert_eq!(
+            data.entity_or_else(DataRef::Null, fallback).id(),
+            root.entity().unwrap()
+        );
+        assert_eq!(
+            data.entity_or_else(missing, fallback).id(),
+            root.entity().unwrap()
+        );
+        assert_eq!(
+            data.entity_or(root, data.entity(root)).id(),
+            root.entity().unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "strict-null")]
+    #[should_panic]
+    fn entity_or_else() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        data.entity_or_else(DataRef::Null, || data.entity(root));
+    }
+
+    #[test]
+    fn map_component() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let result = data.map_component(root, |component: &mut SomeCompoennt| {
+            component.data += 1;
+            component.data
+        });
+        assert_eq!(result, Some(22));
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 22);
+        assert_eq!(
+            data.map_component(DataRef::Null, |_: &mut SomeCompoennt| ()),
+            None
+        );
+    }
+
+    #[test]
+    fn shadowed_entities() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let b = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(b).unwrap().entity;
+        data.get_component_mut::<SomeCompoennt>(a).unwrap();
+        assert!(data.is_shadowed(a));
+        assert!(!data.is_shadowed(b));
+        assert!(!data.is_shadowed(data.resolve(a)));
+        assert_eq!(
+            data.shadowed_entities().collect::<Vec<_>>(),
+            vec![(a, data.resolve(a))]
+        );
+    }
+
+    #[test]
+    fn serialize_dynamic_subset() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let a = data.spawn_dynamic(SomeCompoennt { data: 111 });
+        let b = data.spawn_dynamic(SomeCompoennt { data: 222 });
+        data.spawn_dynamic(SomeCompoennt { data: 333 });
+        let ron = data.serialize_dynamic_subset(&[a, b]).unwrap();
+        assert!(ron.contains("111"));
+        assert!(ron.contains("222"));
+        assert!(!ron.contains("333"));
+    }
+
+    #[test]
+    fn get_component_mut_untracked() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let entity = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let tick = data.last_change_tick();
+        data.get_component_mut_untracked(entity, |component: &mut SomeCompoennt| {
+            component.data = 2;
+        })
+        .unwrap();
+        assert!(data.changed_since(tick).is_empty());
+        assert_eq!(data.get_component::<SomeCompoennt>(entity).unwrap().data, 2);
+        data.get_component_mut::<SomeCompoennt>(entity)
+            .unwrap()
+            .data = 3;
+        assert_eq!(data.changed_since(tick), vec![entity.entity().unwrap()]);
+    }
+
+    #[test]
+    fn extend_static() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let refs = data.extend_static([SomeCompoennt { data: 1 }, SomeCompoennt { data: 2 }]);
+        assert_eq!(refs.len(), 2);
+        assert!(refs.iter().all(DataRef::is_static));
+        assert_eq!(
+            data.get_component::<SomeCompoennt>(refs[0]).unwrap().data,
+            1
+        );
+        assert_eq!(
+            data.get_component::<SomeCompoennt>(refs[1]).unwrap().data,
+            2
+        );
+    }
+
+    #[test]
+    fn data_ref_string_form() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let root = data.modify_static_data(setup_data);
+        let entity = data.spawn_dynamic(SomeRef { entity: root });
+        data.spawn_dynamic(SomeRef { entity });
+        data.spawn_dynamic(SomeRef {
+            entity: DataRef::Null,
+        });
+        for ptr in [DataRef::Null, root, entity] {
+            let json = serde_json::to_string(&ptr).unwrap();
+            assert_eq!(json, format!("\"{ptr}\""));
+            assert_eq!(serde_json::from_str::<DataRef>(&json).unwrap(), ptr);
+        }
+        assert_eq!(root.to_string(), "static:1v1");
+        assert_eq!(entity.to_string(), "dynamic:0:0v1");
+        assert!("dynamic:0v1".parse::<DataRef>().is_err());
+        let ron = data.serialize_dynamic_ron().unwrap();
+        assert!(ron.contains("\"static:1v1\""));
+        assert!(ron.contains("\"dynamic:0:0v1\""));
+        assert!(ron.contains("\"null\""));
+        let mut loaded = DataWorlds::from_scenes(&type_registry, None, None);
+        loaded
+            .load_dynamic_from_json(&type_registry, &data.serialize_dynamic_json().unwrap())
+            .unwrap();
+        let refs = loaded
+            .iter_dynamic()
+            .map(|(_, entity)| entity.get::<SomeRef>().unwrap().entity)
+            .collect::<Vec<_>>();
+        assert_eq!(refs, vec![root, entity, DataRef::Null]);
+    }
+
+    #[test]
+    fn try_transfer() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let missing = data.modify_static_data(|mut commands: Commands| commands.spawn_empty().id());
+        data.modify_static_data(move |mut commands: Commands| commands.entity(missing).despawn());
+        assert_eq!(
+            data.try_transfer(DataRef::Static(missing)),
+            Err(TransferError::Missing(missing))
+        );
+        assert_eq!(
+            data.try_transfer(DataRef::Null),
+            Err(TransferError::NullReference)
+        );
+        let copy = data.try_transfer(root).unwrap();
+        assert!(copy.is_dynamic());
+        assert_eq!(data.try_transfer(root), Ok(copy));
+        assert_eq!(data.try_transfer(copy), Ok(copy));
+    }
+
+    #[test]
+    fn frozen() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let entity = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        data.set_frozen(true);
+        assert!(matches!(data.get_mut(root), DataMut::Missing));
+        assert_eq!(
+            data.try_transfer(root),
+            Err(TransferError::Frozen(root.entity().unwrap()))
+        );
+        assert_eq!(data.transferred_count(), 0);
+        assert!(matches!(data.get_mut(entity), DataMut::Found(_)));
+        data.set_frozen(false);
+        assert!(matches!(data.get_mut(root), DataMut::Moved { .. }));
+    }
+
+    #[test]
+    fn children_of() {
+        #[derive(Component)]
+        struct Children(Vec<DataRef>);
+        impl AsRef<[DataRef]> for Children {
+            fn as_ref(&self) -> &[DataRef] {
+                &self.0
+            }
+        }
+
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let children = data.extend_static([SomeCompoennt { data: 1 }, SomeCompoennt { data: 2 }]);
+        let dynamic_child = data.spawn_dynamic(SomeCompoennt { data: 3 });
+        let parent = data.spawn_dynamic(Children(vec![children[0], children[1], dynamic_child]));
+        let values = data
+            .children_of::<Children>(parent)
+            .into_iter()
+            .map(|child| child.get::<SomeCompoennt>().unwrap().data)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![1, 2, 3]);
+        assert!(data.children_of::<Children>(children[0]).is_empty());
+    }
+
+    #[test]
+    fn swap_dynamic_world() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 1;
+        let entity = data.spawn_dynamic(SomeCompoennt { data: 2 });
+        let world = data.take_dynamic_world();
+        assert_eq!(data.dynamic_entity_count(), 0);
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 21);
+        let mut replacement = World::new();
+        let other = replacement.spawn(SomeCompoennt { data: 3 }).id();
+        let previous = data.set_dynamic_world(replacement);
+        assert_eq!(previous.entities().len(), 0);
+        let other = DataRef::Dynamic(LayerId::BASE, other);
+        assert_eq!(data.get_component::<SomeCompoennt>(other).unwrap().data, 3);
+        assert!(data.dynamic_world().contains_resource::<AppTypeRegistry>());
+        data.set_dynamic_world(world);
+        assert_eq!(data.get_component::<SomeCompoennt>(entity).unwrap().data, 2);
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 1);
+        assert!(data.resolve(root).is_dynamic());
+    }
+
+    #[test]
+    fn garbage_collect() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(root).unwrap().entity;
+        data.get_component_mut::<SomeCompoennt>(a).unwrap().data = 1;
+        let child = data.spawn_dynamic(SomeCompoennt { data: 2 });
+        let parent = data.spawn_dynamic(SomeRef { entity: child });
+        let orphan = data.spawn_dynamic(SomeRef { entity: parent });
+        let static_orphan = data.extend_static([SomeCompoennt { data: 3 }])[0];
+        let transferred_orphan = data.transfer_recursive(static_orphan);
+        assert_eq!(data.garbage_collect(&[root, parent]), 2);
+        assert!(data.contains(data.resolve(a)) && data.resolve(a).is_dynamic());
+        assert!(data.contains(child));
+        assert!(data.contains(parent));
+        assert!(!data.contains(orphan));
+        assert!(!data.contains(transferred_orphan));
+        assert_eq!(data.transferred_count(), 1);
+    }
+
+    #[test]
+    fn preserve_resource() {
+        #[derive(Resource)]
+        struct Preserved(i32);
+        #[derive(Resource)]
+        struct Dropped;
+
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        data.dynamic_world_mut().insert_resource(Preserved(1));
+        data.dynamic_world_mut().insert_resource(Dropped);
+        data.preserve_resource::<Preserved>();
+        data.reload_dynamic_data(DynamicSceneBundle::default());
+        assert_eq!(data.dynamic_world().resource::<Preserved>().0, 1);
+        assert!(!data.dynamic_world().contains_resource::<Dropped>());
+        assert!(data.dynamic_world().contains_resource::<AppTypeRegistry>());
+
+        let type_registry = data.type_registry().clone();
+        let json = data.serialize_dynamic_json().unwrap();
+        data.load_dynamic_from_json(&type_registry, &json).unwrap();
+        assert_eq!(data.dynamic_world().resource::<Preserved>().0, 1);
+        let snapshot = data.snapshot();
+        data.restore(snapshot).unwrap();
+        assert_eq!(data.dynamic_world().resource::<Preserved>().0, 1);
+        let path = std::env::temp_dir().join("data_world_preserve_resource.ron");
+        data.save_dynamic_to_file(&path).unwrap();
+        data.load_dynamic_from_file_preserving_ids(&type_registry, &path)
+            .unwrap();
+        assert_eq!(data.dynamic_world().resource::<Preserved>().0, 1);
+    }
+
+    #[test]
+    fn entity_mut_static_readonly() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let entity = data.entity_mut_static_readonly(root).unwrap();
+        assert_eq!(entity.id(), root.entity().unwrap());
+        assert_eq!(entity.get::<SomeCompoennt>().unwrap().data, 21);
+        assert_eq!(data.transferred_count(), 0);
+    }
+
+    #[test]
+    fn same_target() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(root).unwrap().entity;
+        assert_eq!(DataRef::null(), DataRef::Null);
+        let copy = data.try_transfer(root).unwrap();
+        assert_ne!(root, copy);
+        assert!(root.same_target(&copy, |ptr| data.resolve(ptr)));
+        assert!(!root.same_target(&a, |ptr| data.resolve(ptr)));
+        assert!(DataRef::null().same_target(&DataRef::Null, |ptr| data.resolve(ptr)));
+    }
+
+    #[test]
+    fn save_compressed() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        for i in 0..100 {
+            data.spawn_dynamic(SomeCompoennt { data: i });
+        }
+        let path = std::env::temp_dir().join("data_world_save_compressed.ron.z");
+        data.save_dynamic_compressed(&path, 9).unwrap();
+        let size = fs::metadata(&path).unwrap().len() as usize;
+        assert!(size < data.serialize_dynamic_ron().unwrap().len());
+        let mut loaded = DataWorlds::from_scenes(&type_registry, None, None);
+        loaded
+            .load_dynamic_compressed(&type_registry, &path)
+            .unwrap();
+        assert_eq!(
+            loaded.serialize_dynamic_ron().unwrap(),
+            data.serialize_dynamic_ron().unwrap()
+        );
+        data.save_dynamic_to_file(&path).unwrap();
+        assert!(matches!(
+            loaded.load_dynamic_compressed(&type_registry, &path),
+            Err(LoadError::InvalidHeader)
+        ));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn visit_refs() {
+        #[derive(Debug, Default, Reflect, Component)]
+        #[reflect(Component)]
+        struct SomeRefs(Vec<DataRef>);
+
+        let type_registry = type_registry();
+        type_registry.write().register::<SomeRefs>();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let root = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(root).unwrap().entity;
+        let b = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let entity = data.spawn_dynamic((SomeRef { entity: root }, SomeRefs(vec![a, b])));
+        let mut refs = Vec::new();
+        data.visit_refs(entity, |ptr| refs.push(ptr));
+        refs.sort_by_key(|ptr| ptr.to_string());
+        let mut expected = vec![root, a, b];
+        expected.sort_by_key(|ptr| ptr.to_string());
+        assert_eq!(refs, expected);
+    }
+
+    #[test]
+    fn rewrite_refs() {
+        #[derive(Debug, Reflect)]
+        enum SomeEnum {
+            Empty,
+            Ref(DataRef),
+        }
+        #[derive(Debug, Reflect, Component)]
+        #[reflect(Component)]
+        struct SomeRefs(Vec<SomeEnum>);
+
+        let type_registry = type_registry();
+        type_registry.write().register::<SomeRefs>();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let root = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(root).unwrap().entity;
+        let copy = data.try_transfer(a).unwrap();
+        let entity = data.spawn_dynamic((
+            SomeRef { entity: a },
+            SomeRefs(vec![SomeEnum::Empty, SomeEnum::Ref(a)]),
+        ));
+        data.rewrite_refs(entity, |ptr| if ptr == a { copy } else { ptr });
+        assert_eq!(data.get_component::<SomeRef>(entity).unwrap().entity, copy);
+        let refs = &data.get_component::<SomeRefs>(entity).unwrap().0;
+        assert!(matches!(refs[0], SomeEnum::Empty));
+        assert!(matches!(refs[1], SomeEnum::Ref(ptr) if ptr == copy));
+        data.rewrite_refs(root, |ptr| if ptr == a { copy } else { ptr });
+        assert_eq!(data.get_component::<SomeRef>(root).unwrap().entity, copy);
+        assert_eq!(
+            data.static_world()
+                .get::<SomeRef>(root.entity().unwrap())
+                .unwrap()
+                .entity,
+            a
+        );
+    }
+
+    #[test]
+    fn clear_dynamic() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 1;
+        data.spawn_dynamic(SomeCompoennt { data: 2 });
+        data.clear_dynamic();
+        assert_eq!(data.dynamic_entity_count(), 0);
+        assert_eq!(data.transferred_count(), 0);
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 21);
+        assert!(data.dynamic_world().contains_resource::<AppTypeRegistry>());
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 3;
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 3);
+    }
+
+    #[test]
+    fn from_scenes_async() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let root = data.modify_static_data(setup_data);
+        let entity = data.spawn_dynamic(SomeRef { entity: root });
+        let static_path = std::env::temp_dir().join("data_world_from_scenes_async_static.ron");
+        let dynamic_path = std::env::temp_dir().join("data_world_from_scenes_async_dynamic.ron");
+        data.save_static_to_file(&static_path).unwrap();
+        data.save_dynamic_to_file(&dynamic_path).unwrap();
+        let task = DataWorlds::from_scenes_async(
+            &type_registry,
+            Some(static_path.clone()),
+            Some(dynamic_path.clone()),
+        );
+        let loaded = bevy_tasks::block_on(task).unwrap();
+        fs::remove_file(static_path).unwrap();
+        fs::remove_file(dynamic_path).unwrap();
+        let ptr = loaded.get_component::<SomeRef>(entity).unwrap().entity;
+        assert_eq!(loaded.get_component::<SomeCompoennt>(ptr).unwrap().data, 21);
+    }
+
+    #[test]
+    fn collect_component() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(root).unwrap().entity;
+        let entity = data.spawn_dynamic(SomeRef { entity: a });
+        let refs = [a, DataRef::Null, root, entity];
+        let values = data.collect_component::<SomeRef>(&refs);
+        assert_eq!(
+            values,
+            vec![
+                None,
+                None,
+                Some(SomeRef { entity: a }),
+                Some(SomeRef { entity: a })
+            ]
+        );
+        assert_eq!(
+            data.get_components::<SomeRef>(&refs)
+                .filter(Option::is_some)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn run_dynamic_system_once() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let a = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let b = data.spawn_dynamic(SomeCompoennt { data: 2 });
+        let count = data.run_dynamic_system_once(|mut query: Query<&mut SomeCompoennt>| {
+            query
+                .iter_mut()
+                .map(|mut component| component.data *= 10)
+                .count()
+        });
+        assert_eq!(count, 2);
+        assert_eq!(data.get_component::<SomeCompoennt>(a).unwrap().data, 10);
+        assert_eq!(data.get_component::<SomeCompoennt>(b).unwrap().data, 20);
+    }
+
+    #[test]
+    fn transfer_many_components() {
+        macro_rules! components {
+            ($($name:ident),*) => {
+                $(
+                    #[derive(Debug, Default, Reflect, Component)]
+                    #[reflect(Component)]
+                    struct $name(u32);
+                )*
+                fn register(type_registry: &AppTypeRegistry) {
+                    let mut guard = type_registry.write();
+                    $(guard.register::<$name>();)*
+                }
+                fn bundle(i: u32) -> ($($name,)*) {
+                    ($($name(i),)*)
+                }
+            };
         }
-        // FIXME: ReflectSerialize should be defined by Entity, but it isn't for some reason
-        let data = {
-            let data = world.resource::<DataWorlds>();
-            let static_ron = data.serialize_static_ron().unwrap();
-            let dynamic_ron = data.serialize_dynamic_ron().unwrap();
-            (static_ron, dynamic_ron)
+        components!(H, G, F, E, D, C, B, A);
+
+        let type_registry = type_registry();
+        register(&type_registry);
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let refs = data.extend_static((0..1000).map(bundle));
+        let start = std::time::Instant::now();
+        for &ptr in &refs {
+            data.try_transfer(ptr).unwrap();
+        }
+        debug!(
+            "transferred {} entities in {:?}",
+            refs.len(),
+            start.elapsed()
+        );
+        assert_eq!(data.dynamic_entity_count(), 1000);
+        assert_eq!(data.get_component::<A>(refs[999]).unwrap().0, 999);
+        assert_eq!(data.get_component::<H>(refs[999]).unwrap().0, 999);
+        let names = data
+            .dynamic_world()
+            .components()
+            .iter()
+            .map(|info| info.name().rsplit("::").next().unwrap())
+            .filter(|name| name.len() == 1)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["A", "B", "C", "D", "E", "F", "G", "H"]);
+    }
+
+    #[test]
+    fn transfer_skips_non_rust_components() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(|world: &mut World| {
+            // SAFETY: the component has no drop function and the layout matches the inserted value.
+            let component_id = world.init_component_with_descriptor(unsafe {
+                ComponentDescriptor::new_with_layout(
+                    "dynamic_component",
+                    StorageType::Table,
+                    Layout::new::<u64>(),
+                    None,
+                )
+            });
+            let mut entity = world.spawn(SomeCompoennt { data: 1 });
+            OwningPtr::make(0u64, |ptr| {
+                // SAFETY: `ptr` points to a value matching the component layout.
+                unsafe {
+                    entity.insert_by_id(component_id, ptr);
+                }
+            });
+            DataRef::Static(entity.id())
+        });
+        data.set_strict_transfer(true);
+        assert!(matches!(
+            data.try_transfer(root),
+            Err(TransferError::NotRustType { .. })
+        ));
+        data.set_strict_transfer(false);
+        let copy = data.try_transfer(root).unwrap();
+        let entity = data.get(copy).unwrap();
+        assert_eq!(entity.get::<SomeCompoennt>().unwrap().data, 1);
+        assert_eq!(entity.archetype().components().count(), 1);
+    }
+
+    #[test]
+    fn from_scenes_with_capacity() {
+        let mut data =
+            DataWorlds::from_scenes_with_capacity(&type_registry(), None, None, 100, 200);
+        let refs = data.extend_static((0..100).map(|data| SomeCompoennt { data }));
+        for data_value in 0..200 {
+            data.spawn_dynamic(SomeCompoennt { data: data_value });
+        }
+        assert_eq!(data.static_entity_count(), 100);
+        assert_eq!(data.dynamic_entity_count(), 200);
+        assert_eq!(
+            data.get_component::<SomeCompoennt>(refs[99]).unwrap().data,
+            99
+        );
+    }
+
+    #[test]
+    fn serialize_dynamic_ron_checked() {
+        #[derive(Debug, Clone, Reflect)]
+        #[reflect_value]
+        struct Opaque;
+        #[derive(Debug, Reflect, Component)]
+        #[reflect(Component)]
+        struct Unserializable(Opaque);
+        #[derive(Debug, Clone, Reflect, Serialize)]
+        #[reflect_value(Serialize)]
+        struct WriteOnly(u32);
+        #[derive(Debug, Reflect, Component)]
+        #[reflect(Component)]
+        struct Undeserializable(WriteOnly);
+
+        let type_registry = type_registry();
+        {
+            let mut guard = type_registry.write();
+            guard.register::<Opaque>();
+            guard.register::<Unserializable>();
+            guard.register::<WriteOnly>();
+            guard.register::<Undeserializable>();
+        }
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let entity = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        assert!(data.serialize_dynamic_ron_checked().is_ok());
+        data.insert_component(entity, Unserializable(Opaque));
+        let err = data.serialize_dynamic_ron_checked().unwrap_err();
+        assert!(matches!(err, SerCheckError::Ron(_)));
+        assert!(err.to_string().contains("Opaque"));
+
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        data.spawn_dynamic(Undeserializable(WriteOnly(1)));
+        let err = data.serialize_dynamic_ron_checked().unwrap_err();
+        assert!(matches!(err, SerCheckError::RoundTrip(_)));
+        assert!(err.to_string().contains("WriteOnly"));
+    }
+
+    #[test]
+    fn skip_on_transfer() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        data.skip_on_transfer::<SomeRef>();
+        let copy = data.try_transfer(root).unwrap();
+        let entity = data.get(copy).unwrap();
+        assert!(!entity.contains::<SomeRef>());
+        assert_eq!(entity.get::<SomeCompoennt>().unwrap().data, 21);
+        assert!(data
+            .static_world()
+            .get::<SomeRef>(root.entity().unwrap())
+            .is_some());
+    }
+
+    #[test]
+    fn entity_location() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        assert_eq!(data.entity_location(DataRef::Null), None);
+        assert_eq!(data.entity_location(root), Some(WorldKind::Static));
+        data.get_component_mut::<SomeCompoennt>(root).unwrap();
+        assert_eq!(data.entity_location(root), Some(WorldKind::Dynamic));
+        let copy = data.resolve(root);
+        data.despawn(copy).unwrap();
+        assert_eq!(data.entity_location(copy), None);
+        assert_eq!(data.entity_location(root), Some(WorldKind::Static));
+    }
+
+    #[test]
+    fn span_fields() {
+        use bevy_log::tracing_subscriber::{layer::Context, prelude::*, registry, Layer};
+        use bevy_utils::tracing::{
+            field::{Field, Visit},
+            span::{Id, Record},
+            subscriber::with_default,
+            Subscriber,
         };
-        world.remove_resource::<DataWorlds>();
-        // TODO: save ron to file and test loading
+        use std::sync::{Arc, Mutex};
+
+        type Recorded = Arc<Mutex<Vec<(String, String, u64)>>>;
+        struct Capture(Recorded);
+        struct Visitor<'a>(&'a Recorded, &'static str);
+        impl Visit for Visitor<'_> {
+            fn record_u64(&mut self, field: &Field, value: u64) {
+                let entry = (self.1.to_string(), field.name().to_string(), value);
+                self.0.lock().unwrap().push(entry);
+            }
+            fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {}
+        }
+        impl<S: Subscriber + for<'a> registry::LookupSpan<'a>> Layer<S> for Capture {
+            fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+                let name = ctx.span(id).unwrap().name();
+                values.record(&mut Visitor(&self.0, name));
+            }
+        }
+
+        let recorded = Recorded::default();
+        let subscriber = registry().with(Capture(recorded.clone()));
+        with_default(subscriber, || {
+            let mut data = DataWorlds::from_scenes(
+                &type_registry(),
+                None,
+                Some(DynamicSceneBundle::default()),
+            );
+            let root = data.modify_static_data(setup_data);
+            data.try_transfer(root).unwrap();
+            data.reload_dynamic_data(DynamicSceneBundle::default());
+        });
+        let recorded = recorded.lock().unwrap();
+        let get = |span: &str, field: &str| {
+            recorded
+                .iter()
+                .filter(|(s, f, _)| s == span && f == field)
+                .map(|(_, _, value)| *value)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(get("create_static_data_world", "entity_count"), vec![0]);
+        assert_eq!(get("create_dynamic_data_world", "entity_count"), vec![1, 1]);
+        assert_eq!(get("transfer_data", "component_count"), vec![2]);
+    }
+
+    #[test]
+    fn reload_dynamic_from_ron() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 1;
+        let entity = data.spawn_dynamic(SomeRef { entity: root });
+        let ron = data.serialize_dynamic_ron().unwrap();
+        data.spawn_dynamic(SomeCompoennt { data: 2 });
+        data.reload_dynamic_from_ron(&ron).unwrap();
+        assert_eq!(data.serialize_dynamic_ron().unwrap(), ron);
+        assert_eq!(data.get_component::<SomeRef>(entity).unwrap().entity, root);
+        assert!(data.reload_dynamic_from_ron("(").is_err());
+    }
+
+    #[test]
+    fn merge_static_scene() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let mut world = World::new();
+        world.insert_resource(type_registry.clone());
+        let root = setup_data(&mut world);
+        let base = DynamicScene::from_world(&world);
+        let index = base
+            .entities
+            .iter()
+            .position(|entity| Some(entity.entity) == root.entity())
+            .unwrap();
+        let base_root = data.merge_static_scene(base).unwrap()[index];
+
+        let mut world = World::new();
+        world.insert_resource(type_registry.clone());
+        world.spawn_empty();
+        world.spawn_empty();
+        let a = world.spawn(SomeRef { entity: base_root }).id();
+        let b = world
+            .spawn(SomeRef {
+                entity: DataRef::Static(a),
+            })
+            .id();
+        let extra = DynamicSceneBuilder::from_world(&world)
+            .extract_entities([a, b].into_iter())
+            .build();
+        let extra_refs = data.merge_static_scene(extra).unwrap();
+
+        assert_eq!(data.static_entity_count(), 4);
+        let a = data.get_component::<SomeRef>(extra_refs[1]).unwrap().entity;
+        assert_eq!(a, extra_refs[0]);
+        let root = data.get_component::<SomeRef>(a).unwrap().entity;
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 21);
+        let leaf = data.get_component::<SomeRef>(root).unwrap().entity;
+        assert_eq!(data.get_component::<SomeCompoennt>(leaf).unwrap().data, 42);
+    }
+
+    #[test]
+    fn reused_ids() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let old = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        assert!(data.is_valid(old));
+        assert!(!data.is_reused(old));
+        data.despawn(old).unwrap();
+        assert!(!data.is_valid(old));
+        assert!(!data.is_reused(old));
+        let new = data.spawn_dynamic(SomeCompoennt { data: 2 });
+        assert_eq!(old.entity().unwrap().index(), new.entity().unwrap().index());
+        assert!(data.is_valid(new));
+        assert!(!data.is_valid(old));
+        assert!(data.is_reused(old));
+        assert!(data.get(old).is_none());
+        assert!(matches!(data.get_mut(old), DataMut::Missing));
+        assert_eq!(data.get_component::<SomeCompoennt>(new).unwrap().data, 2);
+    }
+
+    #[test]
+    fn data_formats() {
+        fn round_trip<F: DataFormat>(format: F) {
+            let type_registry = type_registry();
+            let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+            let root = data.modify_static_data(setup_data);
+            data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 1;
+            let entity = data.spawn_dynamic(SomeRef { entity: root });
+            let bytes = data.serialize_dynamic(&format).unwrap();
+            let mut loaded = DataWorlds::from_scenes(&type_registry, None, None);
+            loaded
+                .load_dynamic(&format, &type_registry, &bytes)
+                .unwrap();
+            assert_eq!(loaded.serialize_dynamic(&format).unwrap(), bytes);
+            assert_eq!(
+                loaded.get_component::<SomeRef>(entity).unwrap().entity,
+                root
+            );
+            assert!(matches!(
+                loaded.load_dynamic(&format, &type_registry, b"{"),
+                Err(LoadError::Format(_))
+            ));
+        }
+        round_trip(RonFormat);
+        round_trip(JsonFormat);
+    }
+
+    #[test]
+    fn dynamic_resource() {
+        #[derive(Debug, Default, Resource, Reflect)]
+        #[reflect(Resource)]
+        struct Counter(u32);
+
+        let type_registry = type_registry();
+        type_registry.write().register::<Counter>();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        assert!(data.dynamic_resource::<Counter>().is_none());
+        data.insert_dynamic_resource(Counter(7));
+        assert_eq!(data.dynamic_resource::<Counter>().unwrap().0, 7);
+        data.insert_dynamic_resource(AppTypeRegistry::default());
+        assert!(data
+            .dynamic_world()
+            .resource::<AppTypeRegistry>()
+            .read()
+            .get(TypeId::of::<Counter>())
+            .is_some());
+        let ron = data.serialize_dynamic_ron().unwrap();
+        data.reload_dynamic_from_ron(&ron).unwrap();
+        assert_eq!(data.dynamic_resource::<Counter>().unwrap().0, 7);
+        data.skip_resource::<Counter>();
+        assert!(!data.serialize_dynamic_ron().unwrap().contains("Counter"));
+    }
+
+    #[test]
+    fn build_index() {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Component)]
+        struct ItemId(u32);
+
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let a = data.modify_static_data(|world: &mut World| world.spawn(ItemId(1)).id());
+        let a = DataRef::Static(a);
+        let b = data.spawn_dynamic(ItemId(2));
+        assert_eq!(data.lookup(&ItemId(1)), None);
+        data.build_index::<ItemId>();
+        assert_eq!(data.lookup(&ItemId(1)), Some(a));
+        assert_eq!(data.lookup(&ItemId(2)), Some(b));
+        assert_eq!(data.lookup(&ItemId(3)), None);
+        data.invalidate_index::<ItemId>();
+        assert_eq!(data.lookup(&ItemId(2)), None);
+    }
+
+    #[test]
+    fn serialize_empty_worlds() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let static_ron = data.serialize_static_ron().unwrap();
+        let dynamic_ron = data.serialize_dynamic_ron().unwrap();
+        let path = std::env::temp_dir().join("data_world_serialize_empty_worlds.ron");
+        fs::write(&path, &static_ron).unwrap();
+        data.load_static_from_file(&type_registry, &path).unwrap();
+        data.reload_dynamic_from_ron(&dynamic_ron).unwrap();
+        data.reload_dynamic_from_ron("").unwrap();
+        assert_eq!(data.iter_static().count(), 0);
+        assert_eq!(data.iter_dynamic().count(), 0);
+        assert_eq!(data.serialize_dynamic_ron().unwrap(), dynamic_ron);
+    }
+
+    #[test]
+    fn transfer_all_static() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let b = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(b).unwrap().entity;
+        let c = data
+            .modify_static_data(move |world: &mut World| world.spawn(SomeRef { entity: b }).id());
+        let remap = data.transfer_all_static();
+        assert_eq!(remap.len(), 3);
+        let layer = data.active_layer();
+        let DataRef::Static(b_static) = b else {
+            panic!("expected static reference");
+        };
+        let b_dynamic = DataRef::Dynamic(layer, remap[&b_static]);
+        let c_dynamic = DataRef::Dynamic(layer, remap[&c]);
+        assert_eq!(data.resolve(b), b_dynamic);
+        assert_eq!(
+            data.get_component::<SomeRef>(c_dynamic).unwrap().entity,
+            b_dynamic
+        );
+        assert_eq!(
+            data.get_component::<SomeRef>(b_dynamic).unwrap().entity,
+            data.resolve(a)
+        );
+        assert!(matches!(data.resolve(a), DataRef::Dynamic(..)));
+    }
+
+    #[test]
+    fn entity_world_mut() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let b = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(b).unwrap().entity;
+        data.entity_world_mut(a)
+            .unwrap()
+            .get_mut::<SomeCompoennt>()
+            .unwrap()
+            .data = 7;
+        let dynamic = data.resolve(a);
+        assert!(matches!(dynamic, DataRef::Dynamic(..)));
+        assert_eq!(
+            data.get_component::<SomeCompoennt>(dynamic).unwrap().data,
+            7
+        );
+        assert!(data.entity_world_mut(DataRef::Null).is_none());
+    }
+
+    #[test]
+    fn has_component() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let b = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(b).unwrap().entity;
+        let path = <SomeRef as bevy_reflect::TypePath>::type_path();
+        assert!(data.has_component::<SomeRef>(b));
+        assert!(!data.has_component::<SomeRef>(a));
+        assert!(data.has_component_by_name(b, path));
+        assert!(!data.has_component_by_name(a, path));
+        assert!(!data.has_component_by_name(a, "unknown::Type"));
+        assert!(!data.has_component::<SomeCompoennt>(DataRef::Null));
+    }
+
+    #[test]
+    fn load_with_migrations() {
+        #[derive(Debug, Default, Component, Reflect)]
+        #[reflect(Component)]
+        struct HealthV1 {
+            hp: u32,
+        }
+        #[derive(Debug, Default, Component, Reflect)]
+        #[reflect(Component)]
+        struct Health {
+            value: u32,
+        }
+        struct RenameHp;
+        impl SceneMigration for RenameHp {
+            fn from_version(&self) -> u32 {
+                1
+            }
+            fn apply(&self, scene: &mut DynamicScene) {
+                for entity in &mut scene.entities {
+                    for component in &mut entity.components {
+                        if let Some(old) = HealthV1::from_reflect(component.as_ref()) {
+                            *component = Box::new(Health { value: old.hp });
+                        }
+                    }
+                }
+            }
+        }
+
+        let type_registry = type_registry();
+        {
+            let mut guard = type_registry.write();
+            guard.register::<DataVersion>();
+            guard.register::<HealthV1>();
+            guard.register::<Health>();
+        }
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        data.spawn_dynamic(HealthV1 { hp: 5 });
+        data.insert_dynamic_resource(DataVersion(1));
+        let ron = data.serialize_dynamic_ron().unwrap();
+        let migrations: Vec<Box<dyn SceneMigration>> = vec![Box::new(RenameHp)];
+        data.load_dynamic_from_ron_with_migrations(&ron, &migrations)
+            .unwrap();
+        let (_, entity) = data.iter_dynamic().next().unwrap();
+        assert_eq!(entity.get::<Health>().unwrap().value, 5);
+        assert!(!entity.contains::<HealthV1>());
+        assert_eq!(
+            data.dynamic_resource::<DataVersion>(),
+            Some(&DataVersion(2))
+        );
+    }
+
+    #[test]
+    fn extract_components() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let b = data.modify_static_data(setup_data);
+        let extracted = data.extract_components(b).unwrap();
+        assert_eq!(extracted.components().count(), 2);
+        data.push_layer();
+        let copy = data.spawn_reflected(extracted);
+        assert_ne!(copy, b);
+        assert_eq!(
+            data.get_component::<SomeCompoennt>(copy).unwrap().data,
+            data.get_component::<SomeCompoennt>(b).unwrap().data
+        );
+        assert_eq!(
+            data.get_component::<SomeRef>(copy),
+            data.get_component::<SomeRef>(b)
+        );
+        assert!(data.extract_components(DataRef::Null).is_none());
+        assert!(data.despawn_forced(copy));
+        assert!(data.extract_components(copy).is_none());
+    }
+
+    #[test]
+    fn ref_count() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let b = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let a = data.spawn_dynamic(SomeRef { entity: b });
+        assert_eq!(data.ref_count(b), 1);
+        assert_eq!(data.ref_count(a), 0);
+        assert!(matches!(
+            data.despawn(b),
+            Err(DataError::Referenced { count: 1, .. })
+        ));
+        assert!(data.get(b).is_some());
+        assert!(data.despawn(a).unwrap());
+        assert_eq!(data.ref_count(b), 0);
+        assert!(data.despawn(b).unwrap());
+
+        let b = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        data.spawn_dynamic(SomeRef { entity: b });
+        assert!(data.despawn_forced(b));
+    }
+
+    #[test]
+    fn serialize_dynamic_ron_pretty() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let config = PrettyConfig::new()
+            .indentor("\t".to_string())
+            .struct_names(true);
+        let ron = data.serialize_dynamic_ron_pretty(config).unwrap();
+        assert!(ron.contains("\n\t"));
+        assert!(ron.contains("SomeCompoennt("));
+        let compact = data
+            .serialize_dynamic_ron_pretty(
+                PrettyConfig::new()
+                    .compact_arrays(true)
+                    .new_line(" ".to_string()),
+            )
+            .unwrap();
+        assert!(!compact.contains('\n'));
+        data.reload_dynamic_from_ron(&ron).unwrap();
+    }
+
+    #[test]
+    fn on_transfer() {
+        #[derive(Resource, Default)]
+        struct Runs(u32);
+
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let b = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(b).unwrap().entity;
+        data.set_on_transfer(
+            |In(ptr): In<DataRef>, mut query: Query<&mut SomeCompoennt>, mut runs: ResMut<Runs>| {
+                let DataRef::Dynamic(_, entity) = ptr else {
+                    panic!("expected dynamic reference");
+                };
+                query.get_mut(entity).unwrap().data += 1;
+                runs.0 += 1;
+            },
+        );
+        data.insert_dynamic_resource(Runs::default());
+        data.get_component_mut::<SomeCompoennt>(a).unwrap();
+        data.get_component_mut::<SomeCompoennt>(a).unwrap();
+        data.get_component_mut::<SomeCompoennt>(b).unwrap();
+        assert_eq!(data.dynamic_resource::<Runs>().unwrap().0, 2);
+        assert_eq!(data.get_component::<SomeCompoennt>(a).unwrap().data, 43);
+        assert_eq!(data.get_component::<SomeCompoennt>(b).unwrap().data, 22);
+    }
+
+    #[test]
+    fn world_for() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let dynamic = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        assert!(data.world_for(DataRef::Null).is_none());
+        assert_eq!(data.world_for(root).unwrap().id(), data.static_world.id());
+        assert_eq!(
+            data.world_for(dynamic).unwrap().id(),
+            data.dynamic_world().id()
+        );
+        let DataRef::Static(entity) = root else {
+            panic!("expected static reference");
+        };
+        assert!(data
+            .world_for(root)
+            .unwrap()
+            .get::<SomeRef>(entity)
+            .is_some());
+    }
+
+    #[test]
+    fn content_eq() {
+        let mut a = DataWorlds::from_scenes(&type_registry(), None, None);
+        a.modify_static_data(setup_data);
+        a.spawn_dynamic(SomeCompoennt { data: 1 });
+        a.spawn_dynamic(SomeCompoennt { data: 2 });
+
+        let mut b = DataWorlds::from_scenes(&type_registry(), None, None);
+        b.modify_static_data(setup_data);
+        let unused = b.spawn_dynamic(());
+        b.spawn_dynamic(SomeCompoennt { data: 2 });
+        b.spawn_dynamic(SomeCompoennt { data: 1 });
+        b.despawn(unused).unwrap();
+        assert!(a.content_eq(&b));
+        assert!(b.content_eq(&a));
+
+        b.get_component_mut::<SomeCompoennt>(b.find::<SomeCompoennt>(|c| c.data == 1).unwrap())
+            .unwrap()
+            .data = 3;
+        assert!(!a.content_eq(&b));
+    }
+
+    #[test]
+    fn spawn_static_batch() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let targets = data.spawn_static_batch((0..4).map(|data| SomeCompoennt { data }).collect());
+        let refs = data.spawn_static_batch(
+            targets
+                .iter()
+                .rev()
+                .map(|&entity| SomeRef { entity })
+                .collect(),
+        );
+        assert_eq!(targets.len(), 4);
+        for (index, &ptr) in targets.iter().enumerate() {
+            assert_eq!(
+                data.get_component::<SomeCompoennt>(ptr).unwrap().data,
+                index as i32
+            );
+        }
+        for (index, &ptr) in refs.iter().enumerate() {
+            let target = data.get_component::<SomeRef>(ptr).unwrap().entity;
+            assert_eq!(target, targets[3 - index]);
+        }
+    }
+
+    #[test]
+    fn estimated_size() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        data.modify_static_data(setup_data);
+        let before = data.estimated_size();
+        assert_eq!(before.static_entities, 2);
+        assert_eq!(before.dynamic_entities, 0);
+        assert!(before.static_bytes > 0);
+        assert_eq!(before.dynamic_bytes, 0);
+        data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let after = data.estimated_size();
+        assert!(after.dynamic_bytes > before.dynamic_bytes);
+        data.spawn_dynamic(SomeCompoennt { data: 2 });
+        assert!(data.estimated_size().dynamic_bytes > after.dynamic_bytes);
+        assert_eq!(data.estimated_size().dynamic_entities, 2);
+    }
+
+    #[test]
+    fn data_mut_helpers() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+
+        let missing = data.get_mut(DataRef::Null);
+        assert!(!missing.is_moved());
+        assert_eq!(missing.new_ref(), None);
+        assert!(missing.ok().is_none());
+
+        let moved = data.get_mut(root);
+        assert!(moved.is_moved());
+        let new = moved.new_ref().unwrap();
+        assert!(moved.ok().unwrap().contains::<SomeRef>());
+        assert_eq!(data.resolve(root), new);
+
+        let found = data.get_mut(new);
+        assert!(!found.is_moved());
+        assert_eq!(found.new_ref(), None);
+        assert!(found.ok().unwrap().contains::<SomeRef>());
+    }
+
+    #[test]
+    fn from_fns() {
+        let data = DataWorlds::from_fns(
+            &type_registry(),
+            |world| {
+                setup_data(world);
+            },
+            |world| {
+                world.spawn(SomeCompoennt { data: 1 });
+            },
+        );
+        assert_eq!(data.iter_static().count(), 2);
+        let (_, entity) = data.iter_dynamic().next().unwrap();
+        assert_eq!(entity.get::<SomeCompoennt>().unwrap().data, 1);
+        assert!(data
+            .dynamic_world()
+            .resource::<AppTypeRegistry>()
+            .read()
+            .get(TypeId::of::<SomeRef>())
+            .is_some());
+    }
+
+    #[test]
+    fn load_dynamic_strict() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let ron = data.serialize_dynamic_ron().unwrap();
+        data.load_dynamic_strict(&ron).unwrap();
+        assert_eq!(data.iter_dynamic().count(), 1);
+
+        let path = <SomeCompoennt as bevy_reflect::TypePath>::type_path();
+        let ron = format!(
+            "(resources: {{}}, entities: {{ 0: (components: {{ \"unknown::Type\": (), \"{path}\": (value: 1) }}) }})"
+        );
+        let Err(LoadError::SchemaMismatch(mismatches)) = data.load_dynamic_strict(&ron) else {
+            panic!("expected schema mismatch");
+        };
+        assert_eq!(mismatches.len(), 3);
+        assert!(mismatches.contains(&SchemaMismatch::Unregistered("unknown::Type".to_string())));
+        assert!(mismatches.contains(&SchemaMismatch::UnknownField {
+            type_path: path.to_string(),
+            field: "value".to_string()
+        }));
+        assert!(mismatches.contains(&SchemaMismatch::MissingField {
+            type_path: path.to_string(),
+            field: "data".to_string()
+        }));
+        assert_eq!(data.iter_dynamic().count(), 1);
+    }
+
+    #[test]
+    fn rebase_ref() {
+        let [a, b, c] = [1, 2, 3].map(Entity::from_raw);
+        let remap = HashMap::from([(a, b)]);
+        assert_eq!(
+            super::rebase_ref(DataRef::Static(a), &remap),
+            DataRef::Static(b)
+        );
+        let layer = LayerId(1);
+        assert_eq!(
+            super::rebase_ref(DataRef::Dynamic(layer, a), &remap),
+            DataRef::Dynamic(layer, b)
+        );
+        assert_eq!(
+            super::rebase_ref(DataRef::Static(c), &remap),
+            DataRef::Static(c)
+        );
+        assert_eq!(super::rebase_ref(DataRef::Null, &remap), DataRef::Null);
+    }
+
+    #[test]
+    fn clone_dynamic_deep() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let b = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let a = data.spawn_dynamic(SomeRef { entity: b });
+        let cycle = data.spawn_dynamic(SomeRef {
+            entity: DataRef::Null,
+        });
+        data.get_component_mut::<SomeRef>(cycle).unwrap().entity = cycle;
+
+        let copy = data.clone_dynamic_deep(a);
+        let copy_b = data.get_component::<SomeRef>(copy).unwrap().entity;
+        assert_ne!(copy, a);
+        assert_ne!(copy_b, b);
+        data.get_component_mut::<SomeCompoennt>(copy_b)
+            .unwrap()
+            .data = 2;
+        assert_eq!(data.get_component::<SomeCompoennt>(b).unwrap().data, 1);
+        assert_eq!(data.get_component::<SomeRef>(a).unwrap().entity, b);
+
+        let copy = data.clone_dynamic_deep(cycle);
+        assert_ne!(copy, cycle);
+        assert_eq!(data.get_component::<SomeRef>(copy).unwrap().entity, copy);
+        assert_eq!(data.clone_dynamic_deep(DataRef::Null), DataRef::Null);
+
+        assert!(data.despawn_forced(b));
+        let copy = data.clone_dynamic_deep(a);
+        assert_ne!(copy, a);
+        assert_eq!(data.get_component::<SomeRef>(copy).unwrap().entity, b);
+    }
+
+    #[test]
+    fn prune_transfer_map() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(root).unwrap().entity;
+        data.get_component_mut::<SomeCompoennt>(root).unwrap();
+        data.get_component_mut::<SomeCompoennt>(a).unwrap();
+        let DataRef::Dynamic(_, copy) = data.resolve(root) else {
+            panic!("expected dynamic reference");
+        };
+        data.dynamic_world_mut().despawn(copy);
+        assert!(data.is_shadowed(root));
+        assert_eq!(data.prune_transfer_map(), 1);
+        assert!(!data.is_shadowed(root));
+        assert!(data.is_shadowed(a));
+        assert_eq!(data.resolve(root), root);
+        assert_eq!(data.prune_transfer_map(), 0);
+    }
+
+    #[test]
+    fn read_component() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        let a = data.get_component::<SomeRef>(root).unwrap().entity;
+        assert_eq!(
+            data.read_component::<SomeRef>(root),
+            Some(SomeRef { entity: a })
+        );
+        assert_eq!(data.read_component::<SomeRef>(a), None);
+        assert!(!data.is_shadowed(root));
+    }
+
+    #[test]
+    fn new_empty() {
+        let mut data = DataWorlds::new_empty();
+        assert!(data
+            .type_registry()
+            .read()
+            .get(TypeId::of::<SomeCompoennt>())
+            .is_none());
+        data.register::<SomeCompoennt>();
+        data.spawn_dynamic(SomeCompoennt { data: 123 });
+        let ron = data.serialize_dynamic_ron().unwrap();
+        assert!(ron.contains("SomeCompoennt"));
+        assert!(ron.contains("123"));
+    }
+
+    #[test]
+    fn iter_topological() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Children(Vec<DataRef>);
+
+        let type_registry = type_registry();
+        type_registry.write().register::<Children>();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let leaf = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let left = data.spawn_dynamic(SomeRef { entity: leaf });
+        let right = data.spawn_dynamic(SomeRef { entity: leaf });
+        let root = data.spawn_dynamic(Children(vec![left, right, leaf]));
+        let order = data.iter_topological(&[root]).unwrap();
+        assert_eq!(order.len(), 4);
+        let index = |ptr| order.iter().position(|&other| other == ptr).unwrap();
+        assert!(index(leaf) < index(left));
+        assert!(index(leaf) < index(right));
+        assert!(index(left) < index(root));
+        assert!(index(right) < index(root));
+
+        data.insert_component(leaf, SomeRef { entity: root });
+        let err = data.iter_topological(&[root]).unwrap_err();
+        assert_eq!(err.entities.len(), 3);
+        assert!(err.entities.contains(&root));
+        assert!(err.entities.contains(&leaf));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "static data was mutated")]
+    fn assert_static_unchanged() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        assert!(!data.is_static_mutated());
+        data.checkpoint_static();
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 1;
+        data.spawn_dynamic(SomeCompoennt { data: 2 });
+        assert!(!data.is_static_mutated());
+        data.assert_static_unchanged();
+        data.modify_static_data(|mut query: Query<&mut SomeCompoennt>| {
+            for mut component in &mut query {
+                component.data += 1;
+            }
+        });
+        assert!(data.is_static_mutated());
+        data.assert_static_unchanged();
+    }
+
+    #[test]
+    fn load_session() {
+        let type_registry = type_registry();
+        let mut data = DataWorlds::from_scenes(&type_registry, None, None);
+        let root = data.modify_static_data(setup_data);
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 7;
+        let session = data.serialize_session().unwrap();
+
+        let mut loaded = DataWorlds::from_scenes(&type_registry, None, None);
+        assert_eq!(loaded.modify_static_data(setup_data), root);
+        loaded.load_session(&session).unwrap();
+        assert!(loaded.is_shadowed(root));
+        assert_eq!(loaded.resolve(root), data.resolve(root));
+        assert_eq!(loaded.get_component::<SomeCompoennt>(root).unwrap().data, 7);
+
+        #[derive(Resource)]
+        struct Keep;
+        loaded.insert_dynamic_resource(Keep);
+        loaded.preserve_resource::<Keep>();
+        loaded.load_session(&session).unwrap();
+        assert!(loaded.dynamic_resource::<Keep>().is_some());
+        assert!(loaded.is_shadowed(root));
+    }
+
+    #[test]
+    fn transfer_filter() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        data.set_transfer_filter(|type_id, name| {
+            type_id != TypeId::of::<SomeRef>() && name.contains("SomeCompoennt")
+        });
+        data.get_component_mut::<SomeCompoennt>(root).unwrap();
+        let copy = data.resolve(root);
+        assert!(copy.is_dynamic());
+        assert!(data.has_component::<SomeCompoennt>(copy));
+        assert!(!data.has_component::<SomeRef>(copy));
+    }
+
+    #[test]
+    fn debug_summary() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        data.get_component_mut::<SomeCompoennt>(root).unwrap();
+        data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let output = format!("{data:?}");
+        assert!(output.contains("static_entities: 2"));
+        assert!(output.contains("dynamic_entities: 2"));
+        assert!(output.contains("transferred: 1"));
+        assert!(output.contains("registered_types: "));
+        assert!(!output.contains("SomeCompoennt"));
+        assert!(!output.contains("World {"));
+    }
+
+    #[test]
+    fn reload_dynamic_from_ron_preserves_resources() {
+        #[derive(Resource)]
+        struct Keep(i32);
+
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let ron = data.serialize_dynamic_ron().unwrap();
+        data.insert_dynamic_resource(Keep(5));
+        data.preserve_resource::<Keep>();
+        data.reload_dynamic_from_ron(&ron).unwrap();
+        assert_eq!(data.dynamic_resource::<Keep>().unwrap().0, 5);
+        assert_eq!(data.iter_dynamic().count(), 1);
+    }
+
+    #[test]
+    fn contains_stale_generation() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let ptr = data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let entity = ptr.entity().unwrap();
+        assert!(data.despawn_forced(ptr));
+        let next = format!("dynamic:0:{}v{}", entity.index(), entity.generation() + 1)
+            .parse::<DataRef>()
+            .unwrap();
+        assert!(data.get(next).is_none());
+        assert!(!data.contains(next));
+        assert!(!data.is_valid(next));
+        assert_eq!(data.entity_location(next), None);
+        let reserved = DataRef::Dynamic(
+            LayerId::BASE,
+            data.dynamic_world().entities().reserve_entity(),
+        );
+        assert!(data.get(reserved).is_none());
+        assert!(!data.contains(reserved));
+    }
+
+    #[test]
+    fn commit_keeps_skipped_components() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        data.skip_on_transfer::<SomeRef>();
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 5;
+        data.commit_dynamic_to_static().unwrap();
+        let entity = data.static_world().entity(root.entity().unwrap());
+        assert_eq!(entity.get::<SomeCompoennt>().unwrap().data, 5);
+        assert!(entity.contains::<SomeRef>());
+    }
+
+    #[test]
+    fn layers_discard_changes() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 2;
+        let base_copy = data.resolve(root);
+        data.push_layer();
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 99;
+        assert_ne!(data.resolve(root), base_copy);
+        assert_eq!(
+            data.get_component::<SomeCompoennt>(base_copy).unwrap().data,
+            2
+        );
+        data.push_layer();
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 100;
+        data.pop_layer().unwrap();
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 99);
+        data.pop_layer().unwrap();
+        assert_eq!(data.resolve(root), base_copy);
+        assert_eq!(data.get_component::<SomeCompoennt>(root).unwrap().data, 2);
+        data.push_layer();
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 3;
+        data.commit_dynamic_to_static().unwrap();
+        assert_eq!(data.static_world().iter_entities().count(), 2);
+        assert_eq!(
+            data.static_world()
+                .get::<SomeCompoennt>(root.entity().unwrap())
+                .unwrap()
+                .data,
+            3
+        );
+    }
+
+    #[test]
+    fn commit_dynamic_missing_original() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        let root = data.modify_static_data(setup_data);
+        data.get_component_mut::<SomeCompoennt>(root).unwrap().data = 7;
+        data.modify_static_data(move |world: &mut World| world.despawn(root.entity().unwrap()));
+        data.commit_dynamic_to_static().unwrap();
+        assert_eq!(data.dynamic_entity_count(), 0);
+        let mut query = data.static_world.query::<&SomeCompoennt>();
+        assert!(query
+            .iter(&data.static_world)
+            .any(|component| component.data == 7));
+    }
+
+    #[test]
+    fn migrations_without_version() {
+        let mut data = DataWorlds::from_scenes(&type_registry(), None, None);
+        data.spawn_dynamic(SomeCompoennt { data: 1 });
+        let ron = data.serialize_dynamic_ron().unwrap();
+        data.load_dynamic_from_ron_with_migrations(&ron, &[])
+            .unwrap();
+        assert_eq!(data.dynamic_entity_count(), 1);
+        assert!(data.dynamic_resource::<DataVersion>().is_none());
     }
 }